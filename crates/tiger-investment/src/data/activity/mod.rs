@@ -1,6 +1,13 @@
 use tiger_parse::{Pointer, tiger_tag};
 
-use crate::data::text::IndexedString;
+use crate::{
+    data::{
+        destination::{Destination, Place},
+        text::IndexedString,
+        unlock::{Unlock, UnlockKind},
+    },
+    global_instance::investment_manager,
+};
 
 pub mod graph;
 pub mod interactables;
@@ -17,6 +24,29 @@ impl Activity {
     pub fn new(data: SActivityData, display: SActivityDisplayData) -> Self {
         Self { data, display }
     }
+
+    pub fn destination(&self) -> Option<Destination> {
+        investment_manager()
+            .destinations()
+            .get_destination_by_index(usize::try_from(self.data.destination_index).ok()?)
+    }
+
+    pub fn place(&self) -> Option<Place> {
+        investment_manager()
+            .destinations()
+            .get_place_by_index(usize::try_from(self.data.place_index).ok()?)
+    }
+
+    /// Returns every requirement that has to be met to launch the activity.
+    pub fn requirements(&self) -> Vec<S80805694> {
+        let requirements = &self.display.requirements;
+        requirements
+            .unk10
+            .iter()
+            .chain(requirements.unk20.iter())
+            .cloned()
+            .collect()
+    }
 }
 
 #[tiger_tag(id = 0x8080718D, size = 0x18)]
@@ -39,6 +69,8 @@ pub struct SActivityData {
     pub hash: u32,
     #[tag(offset = 0x8)]
     pub unk8: Vec<i32>,
+    pub destination_index: i16,
+    pub place_index: i16,
     #[tag(offset = 0x20)]
     pub matchmaking: Pointer<S808072F0>, //808072f0, 0x58
     #[tag(offset = 0x38)]
@@ -102,6 +134,15 @@ pub struct S80807300 {
     pub unk_hash: u32,
 }
 
+impl S80807300 {
+    /// Resolves the unlock flag the insertion point is gated by.
+    pub fn unlock(&self) -> Option<Unlock> {
+        investment_manager()
+            .unlocks()
+            .resolve(UnlockKind::Flag, self.unlock_index as usize)
+    }
+}
+
 #[derive(Clone)]
 #[tiger_tag(id = 0x8080B6FE, size = 0x48)]
 pub struct S8080B6FE {
@@ -163,11 +204,20 @@ pub struct S80805691 {
 #[derive(Clone)]
 #[tiger_tag(id = 0x80805694, size = 0x28)]
 pub struct S80805694 {
-    pub unk0: u32,
+    pub unlock_index: u32,
     pub unk4: IndexedString,
     pub unk8: IndexedString,
 }
 
+impl S80805694 {
+    /// Resolves the unlock flag the requirement checks for.
+    pub fn unlock(&self) -> Option<Unlock> {
+        investment_manager()
+            .unlocks()
+            .resolve(UnlockKind::Flag, self.unlock_index as usize)
+    }
+}
+
 #[derive(Clone)]
 #[tiger_tag(id = 0x80805694, size = 0x28)]
 pub struct S80805622 {
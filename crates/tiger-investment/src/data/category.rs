@@ -0,0 +1,71 @@
+use tiger_parse::{Pointer, tiger_tag};
+
+use crate::{data::text::IndexedString, global_instance::investment_manager};
+
+/// Hash of the root category every weapon is in.
+pub const WEAPON_CATEGORY_HASH: u32 = 1;
+
+#[derive(Clone)]
+pub struct ItemCategory {
+    /// Index of the category in the category table.
+    pub index: usize,
+    pub data: SItemCategory,
+}
+
+impl ItemCategory {
+    pub fn new(index: usize, data: SItemCategory) -> Self {
+        Self { index, data }
+    }
+
+    pub fn name(&self) -> String {
+        self.data.name.get().unwrap_or_default()
+    }
+
+    pub fn parents(&self) -> Vec<ItemCategory> {
+        let categories = investment_manager().categories();
+        self.data
+            .parent_category_indices
+            .iter()
+            .filter_map(|i| categories.get_by_index(usize::try_from(*i).ok()?))
+            .collect()
+    }
+
+    /// Returns the chain of categories from the root category down to this one, e.g. Weapon > Auto Rifle.
+    pub fn path(&self) -> Vec<ItemCategory> {
+        investment_manager().categories().path(self.index)
+    }
+
+    pub fn display_path(&self) -> String {
+        self.path()
+            .iter()
+            .map(|c| c.name())
+            .collect::<Vec<_>>()
+            .join(" > ")
+    }
+}
+
+#[tiger_tag(id = 0x80805BA0, size = 0x18)]
+pub struct SItemCategoryList {
+    pub file_size: u64,
+    pub categories: Vec<S80805BA4>,
+}
+
+#[derive(Clone)]
+#[tiger_tag(id = 0x80805BA4, size = 0x10)]
+pub struct S80805BA4 {
+    pub hash: u32,
+    #[tag(offset = 0x8)]
+    pub category: Pointer<SItemCategory>,
+}
+
+#[derive(Clone)]
+#[tiger_tag(id = 0x80805BA6, size = 0x40)]
+pub struct SItemCategory {
+    pub hash: u32,
+    pub visible: u8,
+    #[tag(offset = 0x8)]
+    pub name: IndexedString,
+    pub short_title: IndexedString,
+    pub parent_category_indices: Vec<i16>,
+    pub grouped_category_indices: Vec<i16>,
+}
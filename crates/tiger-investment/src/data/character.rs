@@ -0,0 +1,106 @@
+use tiger_parse::{Pointer, tiger_tag};
+
+use crate::data::text::IndexedString;
+
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Hash, strum::Display, strum::EnumIter, strum::FromRepr,
+)]
+#[repr(u8)]
+pub enum DestinyClass {
+    Titan = 0,
+    Hunter = 1,
+    Warlock = 2,
+}
+
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Hash, strum::Display, strum::EnumIter, strum::FromRepr,
+)]
+#[repr(u8)]
+pub enum DestinyRace {
+    Human = 0,
+    Awoken = 1,
+    Exo = 2,
+}
+
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Hash, strum::Display, strum::EnumIter, strum::FromRepr,
+)]
+#[repr(u8)]
+pub enum DestinyGender {
+    Male = 0,
+    Female = 1,
+}
+
+#[tiger_tag(id = 0x80805B80, size = 0x18)]
+pub struct SClassList {
+    pub file_size: u64,
+    pub classes: Vec<S80805B84>,
+}
+
+#[derive(Clone)]
+#[tiger_tag(id = 0x80805B84, size = 0x10)]
+pub struct S80805B84 {
+    pub hash: u32,
+    #[tag(offset = 0x8)]
+    pub class: Pointer<SClass>,
+}
+
+#[derive(Clone)]
+#[tiger_tag(id = 0x80805B86, size = 0x20)]
+pub struct SClass {
+    pub hash: u32,
+    pub class_type: u8,
+    #[tag(offset = 0x8)]
+    pub name: IndexedString,
+    /// Class names for each gender, indexed by `DestinyGender`
+    pub gendered_names: [IndexedString; 2],
+}
+
+#[tiger_tag(id = 0x80805B88, size = 0x18)]
+pub struct SRaceList {
+    pub file_size: u64,
+    pub races: Vec<S80805B8C>,
+}
+
+#[derive(Clone)]
+#[tiger_tag(id = 0x80805B8C, size = 0x10)]
+pub struct S80805B8C {
+    pub hash: u32,
+    #[tag(offset = 0x8)]
+    pub race: Pointer<SRace>,
+}
+
+#[derive(Clone)]
+#[tiger_tag(id = 0x80805B8E, size = 0x28)]
+pub struct SRace {
+    pub hash: u32,
+    pub race_type: u8,
+    #[tag(offset = 0x8)]
+    pub name: IndexedString,
+    pub description: IndexedString,
+    /// Race names for each gender, indexed by `DestinyGender`
+    pub gendered_names: [IndexedString; 2],
+}
+
+#[tiger_tag(id = 0x80805B90, size = 0x18)]
+pub struct SGenderList {
+    pub file_size: u64,
+    pub genders: Vec<S80805B94>,
+}
+
+#[derive(Clone)]
+#[tiger_tag(id = 0x80805B94, size = 0x10)]
+pub struct S80805B94 {
+    pub hash: u32,
+    #[tag(offset = 0x8)]
+    pub gender: Pointer<SGender>,
+}
+
+#[derive(Clone)]
+#[tiger_tag(id = 0x80805B96, size = 0x10)]
+pub struct SGender {
+    pub hash: u32,
+    pub gender_type: u8,
+    #[tag(offset = 0x8)]
+    pub name: IndexedString,
+}
@@ -0,0 +1,89 @@
+use tiger_parse::{Pointer, tiger_tag};
+
+use crate::{data::text::IndexedString, global_instance::investment_manager};
+
+#[derive(Clone)]
+pub struct Destination {
+    pub data: SDestination,
+}
+
+#[derive(Clone)]
+pub struct Place {
+    pub data: SPlace,
+}
+
+impl Destination {
+    pub fn new(data: SDestination) -> Self {
+        Self { data }
+    }
+
+    pub fn place(&self) -> Option<Place> {
+        investment_manager()
+            .destinations()
+            .get_place_by_index(usize::try_from(self.data.place_index).ok()?)
+    }
+}
+
+impl Place {
+    pub fn new(data: SPlace) -> Self {
+        Self { data }
+    }
+}
+
+#[tiger_tag(id = 0x80805B5E, size = 0x18)]
+pub struct SDestinationList {
+    pub file_size: u64,
+    pub destinations: Vec<S80805B62>,
+}
+
+#[derive(Clone)]
+#[tiger_tag(id = 0x80805B62, size = 0x10)]
+pub struct S80805B62 {
+    pub hash: u32,
+    #[tag(offset = 0x8)]
+    pub destination: Pointer<SDestination>,
+}
+
+#[derive(Clone)]
+#[tiger_tag(id = 0x80805B64, size = 0x38)]
+pub struct SDestination {
+    pub hash: u32,
+    pub place_index: i16,
+    pub icon_index: i16,
+    pub name: IndexedString,
+    pub description: IndexedString,
+    #[tag(offset = 0x20)]
+    pub bubbles: Vec<S80805B68>,
+}
+
+/// A bubble is a single area of a destination, e.g. "The Divide" on Europa.
+#[derive(Clone)]
+#[tiger_tag(id = 0x80805B68, size = 0x14)]
+pub struct S80805B68 {
+    pub hash: u32,
+    pub name: IndexedString,
+    pub description: IndexedString,
+}
+
+#[tiger_tag(id = 0x80805B4F, size = 0x18)]
+pub struct SPlaceList {
+    pub file_size: u64,
+    pub places: Vec<S80805B53>,
+}
+
+#[derive(Clone)]
+#[tiger_tag(id = 0x80805B53, size = 0x10)]
+pub struct S80805B53 {
+    pub hash: u32,
+    #[tag(offset = 0x8)]
+    pub place: Pointer<SPlace>,
+}
+
+#[derive(Clone)]
+#[tiger_tag(id = 0x80805B55, size = 0x18)]
+pub struct SPlace {
+    pub hash: u32,
+    pub name: IndexedString,
+    pub description: IndexedString,
+    pub icon_index: i16,
+}
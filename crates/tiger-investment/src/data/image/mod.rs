@@ -37,15 +37,50 @@ pub struct S80805A07 {
 #[derive(Clone)]
 pub struct InvestmentIcon {
     pub data: SInvestmentIcon,
+    pub tag: TagHash,
 }
 
 impl InvestmentIcon {
     pub fn new(tag: impl Into<TagHash>) -> anyhow::Result<Self> {
+        let tag = tag.into();
         Ok(Self {
             data: package_manager().read_tag_struct::<SInvestmentIcon>(tag)?,
+            tag,
         })
     }
 
+    /// Returns every tag the icon is made up of, along with what it is used for.
+    pub fn tag_hashes(&self) -> Vec<(&'static str, TagHash)> {
+        let mut tags = vec![("icon", self.tag)];
+        for (field, container) in [
+            ("icon.primary", &self.data.primary),
+            ("icon.background", &self.data.background),
+            ("icon.overlay", &self.data.overlay),
+        ] {
+            if container.taghash().is_some() {
+                tags.push((field, container.taghash()));
+            }
+        }
+
+        let textures = self
+            .get_primary_textures()
+            .into_iter()
+            .map(|t| ("icon.primary.textures", t))
+            .chain(
+                self.get_background_textures(None)
+                    .into_iter()
+                    .map(|t| ("icon.background.textures", t)),
+            )
+            .chain(
+                self.get_overlay_textures()
+                    .into_iter()
+                    .map(|t| ("icon.overlay.textures", t)),
+            )
+            .filter(|(_, t)| t.is_some());
+        tags.extend(textures);
+        tags
+    }
+
     pub fn get_background_textures(&self, colorblind_mode: Option<ColorblindMode>) -> Vec<TagHash> {
         if let Some(background) = &self.data.background.0
             && let Some(texhash) = match *background.data {
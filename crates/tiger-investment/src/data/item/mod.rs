@@ -1,23 +1,96 @@
 use tiger_parse::{Pointer, PointerOptional, tiger_tag};
+use tiger_pkg::TagHash;
 use tiger_tag::{WideHash, WideTag};
 
 use crate::{
     data::{
+        category::ItemCategory,
+        character::{DestinyClass, DestinyGender},
         image::{InvestmentIcon, SInvestmentIcon},
+        objective::SObjective,
+        plug::S80807A0C,
         text::IndexedString,
     },
     global_instance::investment_manager,
 };
 
+pub mod quest;
+pub mod roll;
+pub mod set;
+
 #[derive(Clone)]
 pub struct InventoryItem {
     pub data: SInventoryItem,
     pub display: SInventoryItemDisplay,
+    pub restriction: ItemRestriction,
+    /// Tag the item's data was read from
+    pub data_tag: TagHash,
+    /// Tag the item's display data was read from
+    pub display_tag: TagHash,
+}
+
+/// Restricts who is able to use an item.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ItemRestriction {
+    /// The only class that can use the item, `None` if every class can.
+    pub class: Option<DestinyClass>,
+}
+
+impl ItemRestriction {
+    pub fn allows(&self, class: DestinyClass) -> bool {
+        self.class.is_none_or(|c| c == class)
+    }
+}
+
+/// Rarity of an item.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    strum::Display,
+    strum::EnumIter,
+    strum::EnumString,
+    strum::FromRepr,
+)]
+#[strum(ascii_case_insensitive)]
+#[repr(u8)]
+pub enum ItemTier {
+    Unknown = 0,
+    Currency = 1,
+    Basic = 2,
+    Common = 3,
+    Rare = 4,
+    Legendary = 5,
+    Exotic = 6,
+}
+
+/// Strings of an item that differ based on the gender of the character viewing them.
+#[derive(Clone)]
+pub struct GenderedStrings {
+    pub gender: Option<DestinyGender>,
+    pub name: Option<String>,
+    pub item_type: Option<String>,
 }
 
 impl InventoryItem {
-    pub fn new(data: SInventoryItem, display: SInventoryItemDisplay) -> Self {
-        Self { data, display }
+    pub fn new(data: &WideTag<SInventoryItem>, display: &WideTag<SInventoryItemDisplay>) -> Self {
+        let restriction = ItemRestriction {
+            class: DestinyClass::from_repr(data.class_type),
+        };
+        Self {
+            data: data.0.clone(),
+            display: display.0.clone(),
+            restriction,
+            data_tag: data.1,
+            display_tag: display.1,
+        }
+    }
+
+    pub fn tier(&self) -> Option<ItemTier> {
+        ItemTier::from_repr(self.data.tier_type)
     }
 
     pub fn icon(&self) -> Option<InvestmentIcon> {
@@ -25,6 +98,85 @@ impl InventoryItem {
             .icons()
             .get_index(self.display.icon_index as usize)
     }
+
+    /// Returns the categories the item is directly in.
+    pub fn categories(&self) -> Vec<ItemCategory> {
+        let categories = investment_manager().categories();
+        self.data
+            .category_indices
+            .iter()
+            .filter_map(|i| categories.get_by_index(usize::try_from(*i).ok()?))
+            .collect()
+    }
+
+    /// Returns the most specific category of the item, the one with the longest path.
+    pub fn primary_category(&self) -> Option<ItemCategory> {
+        let categories = investment_manager().categories();
+        self.categories()
+            .into_iter()
+            .max_by_key(|c| categories.ancestors(c.index).len())
+    }
+
+    /// Returns the root categories the item is below, e.g. Weapon for an auto rifle.
+    pub fn root_categories(&self) -> Vec<ItemCategory> {
+        let categories = investment_manager().categories();
+        let mut roots: Vec<usize> = self
+            .data
+            .category_indices
+            .iter()
+            .filter_map(|i| usize::try_from(*i).ok())
+            .flat_map(|i| categories.roots_of(i))
+            .collect();
+        roots.sort_unstable();
+        roots.dedup();
+        roots
+            .into_iter()
+            .filter_map(|i| categories.get_by_index(i))
+            .collect()
+    }
+
+    /// Returns true if the item is in the category with `category_hash`, or any category below it.
+    pub fn in_category(&self, category_hash: u32) -> bool {
+        let categories = investment_manager().categories();
+        let Some(ancestor_index) = categories.index_of_hash(category_hash) else {
+            return false;
+        };
+        self.data
+            .category_indices
+            .iter()
+            .any(|i| usize::try_from(*i).is_ok_and(|index| categories.is_in(index, ancestor_index)))
+    }
+
+    /// Returns the gendered variants of the item's strings, if there are any.
+    pub fn gendered_strings(&self) -> Vec<GenderedStrings> {
+        let characters = investment_manager().characters();
+        self.display
+            .gendered_strings
+            .iter()
+            .map(|s| GenderedStrings {
+                gender: usize::try_from(s.gender_index)
+                    .ok()
+                    .and_then(|i| characters.gender_by_index(i))
+                    .and_then(|g| DestinyGender::from_repr(g.gender_type)),
+                name: s.name.get(),
+                item_type: s.item_type.get(),
+            })
+            .collect()
+    }
+
+    /// Returns the objectives that need to be completed for this item, in display order.
+    pub fn objectives(&self) -> Vec<SObjective> {
+        let Some(objectives) = &self.data.objectives.0 else {
+            return Vec::new();
+        };
+
+        let objective_manager = investment_manager().objectives();
+        objectives
+            .objective_indices
+            .iter()
+            .filter_map(|i| objective_manager.get_by_index(usize::try_from(*i).ok()?))
+            .collect()
+    }
 }
 
 #[derive(Clone)]
@@ -84,7 +236,16 @@ pub struct SInventoryItemDisplay {
     pub toast: IndexedString,
     pub flavor: IndexedString,
     pub unkac: u32,
-    pub unkb0: Vec<()>,
+    pub gendered_strings: Vec<S808054A3>,
+}
+
+#[derive(Clone)]
+#[tiger_tag(id = 0x808054A3, size = 0x14)]
+pub struct S808054A3 {
+    pub gender_index: i16,
+    #[tag(offset = 0x4)]
+    pub name: IndexedString,
+    pub item_type: IndexedString,
 }
 
 #[derive(Clone)]
@@ -95,16 +256,45 @@ pub struct SInventoryItem {
     #[tag(offset = 0x18)]
     pub unk18: PointerOptional<()>,
     #[tag(offset = 0x48)]
-    pub unk48: PointerOptional<()>,
+    pub plug: PointerOptional<S80807A0C>,
     #[tag(offset = 0x60)]
     pub sockets: PointerOptional<S808077C0>,
     pub stats_perks: PointerOptional<S80807381>,
-    pub unk70: PointerOptional<()>,
-    pub unk78: PointerOptional<()>,
+    pub objectives: PointerOptional<S808079AC>,
+    pub set_data: PointerOptional<S808079B0>,
     #[tag(offset = 0x88)]
     pub hash: u32,
     #[tag(offset = 0x98)]
     pub unk98: IndexedString,
+    pub class_type: u8,
+    #[tag(offset = 0xa8)]
+    pub category_indices: Vec<i16>,
+    pub equipable_item_set_index: i16,
+    pub tier_type: u8,
+}
+
+#[derive(Clone)]
+#[tiger_tag(id = 0x808079AC, size = 0x10)]
+pub struct S808079AC {
+    pub objective_indices: Vec<i16>,
+}
+
+#[derive(Clone)]
+#[tiger_tag(id = 0x808079B0, size = 0x20)]
+pub struct S808079B0 {
+    pub items: Vec<S808079B4>,
+    pub set_type: u32,
+    /// Index of the quest line item this set belongs to, -1 if this set is not a quest
+    pub quest_line_item_index: i16,
+    pub require_ordered_completion: u8,
+}
+
+#[derive(Clone)]
+#[tiger_tag(id = 0x808079B4, size = 0x8)]
+pub struct S808079B4 {
+    pub item_index: i16,
+    #[tag(offset = 0x4)]
+    pub tracking_value: u32,
 }
 
 #[derive(Clone)]
@@ -121,8 +311,9 @@ pub struct S808077C3 {
     pub single_initial_item_index: i16,
     #[tag(offset = 0x10)]
     pub reusable_plugset_index: i16,
+    /// Plug set the perks of this socket are randomly rolled from
     #[tag(offset = 0x28)]
-    pub reusable_plugset_index_2: i16,
+    pub randomized_plugset_index: i16,
     #[tag(offset = 0x48)]
     pub plug_items: Vec<S808077D5>,
 }
@@ -139,6 +330,7 @@ pub struct S808077D5 {
 pub struct S80807381 {
     pub stats: Vec<S80807386>,
     pub perks: Vec<S80807387>,
+    pub stat_group_index: i16,
 }
 
 #[derive(Clone)]
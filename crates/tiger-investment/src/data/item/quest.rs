@@ -0,0 +1,74 @@
+use crate::{
+    data::{item::InventoryItem, objective::SObjective},
+    global_instance::investment_manager,
+};
+
+/// An ordered chain of quest steps, built from the set data of one of its steps.
+#[derive(Clone)]
+pub struct QuestLine {
+    /// The quest line item the steps belong to, if the game has one for it.
+    pub quest_line: Option<InventoryItem>,
+    pub steps: Vec<QuestStep>,
+    /// Position of the item this chain was built from in `steps`.
+    pub current: usize,
+}
+
+#[derive(Clone)]
+pub struct QuestStep {
+    /// Index of the step's item in the item table.
+    pub item_index: usize,
+    pub item: InventoryItem,
+    pub objectives: Vec<SObjective>,
+}
+
+impl QuestLine {
+    pub fn current_step(&self) -> &QuestStep {
+        &self.steps[self.current]
+    }
+
+    pub fn previous_step(&self) -> Option<&QuestStep> {
+        self.steps.get(self.current.checked_sub(1)?)
+    }
+
+    pub fn next_step(&self) -> Option<&QuestStep> {
+        self.steps.get(self.current + 1)
+    }
+}
+
+impl InventoryItem {
+    /// Returns the quest line this item is a step of, or `None` if the item isn't a quest step.
+    pub fn quest_line(&self) -> Option<QuestLine> {
+        let set_data = self.data.set_data.0.as_ref()?;
+        let quest_line_index = usize::try_from(set_data.quest_line_item_index).ok()?;
+
+        let items = investment_manager().items();
+        let own_index = items.index_of_hash(self.data.hash)?;
+
+        let mut current = None;
+        let mut steps = Vec::with_capacity(set_data.items.len());
+        for entry in &set_data.items {
+            let Ok(item_index) = usize::try_from(entry.item_index) else {
+                continue;
+            };
+            let Some(item) = items.get_item_by_index(item_index) else {
+                continue;
+            };
+
+            if item_index == own_index {
+                current = Some(steps.len());
+            }
+
+            steps.push(QuestStep {
+                item_index,
+                objectives: item.objectives(),
+                item,
+            });
+        }
+
+        Some(QuestLine {
+            quest_line: items.get_item_by_index(quest_line_index),
+            steps,
+            current: current?,
+        })
+    }
+}
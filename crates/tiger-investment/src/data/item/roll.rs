@@ -0,0 +1,292 @@
+use crate::{
+    data::{
+        category::WEAPON_CATEGORY_HASH,
+        item::{InventoryItem, S808077C3},
+        plug::CosmeticKind,
+    },
+    global_instance::investment_manager,
+    manager::socket_plugs,
+};
+
+/// Every perk a weapon can roll with, split up into the columns shown in game.
+#[derive(Clone)]
+pub struct WeaponRolls {
+    pub columns: Vec<PerkColumn>,
+}
+
+/// A single perk socket of a weapon.
+#[derive(Clone)]
+pub struct PerkColumn {
+    /// Index of the socket in the item's socket list.
+    pub socket_index: usize,
+    /// Whether the perks of this column are randomly rolled.
+    pub randomized: bool,
+    /// Index of the plug the weapon comes with when it isn't randomly rolled.
+    pub initial_item_index: Option<usize>,
+    pub pool: Vec<RollPlug>,
+}
+
+#[derive(Clone)]
+pub struct RollPlug {
+    pub item_index: usize,
+    pub item: InventoryItem,
+    /// False for perks that could roll in the past, but no longer drop.
+    pub can_roll: bool,
+}
+
+impl PerkColumn {
+    /// Returns the plugs that can currently roll in this column.
+    pub fn rollable(&self) -> impl Iterator<Item = &RollPlug> {
+        self.pool.iter().filter(|p| p.can_roll)
+    }
+}
+
+impl WeaponRolls {
+    /// Collects the perk columns of a weapon, `None` if the item isn't a weapon or has no perks.
+    pub fn new(item: &InventoryItem) -> Option<Self> {
+        if !item.in_category(WEAPON_CATEGORY_HASH) {
+            return None;
+        }
+        let sockets = item.data.sockets.0.as_ref()?;
+
+        let columns: Vec<PerkColumn> = sockets
+            .data
+            .iter()
+            .enumerate()
+            .filter_map(|(i, socket)| perk_column(i, socket))
+            .collect();
+
+        if columns.is_empty() {
+            None
+        } else {
+            Some(Self { columns })
+        }
+    }
+
+    /// Returns true if any of the columns are randomly rolled.
+    pub fn is_random(&self) -> bool {
+        self.columns.iter().any(|c| c.randomized)
+    }
+
+    /// Returns the perks the weapon comes with when it isn't randomly rolled, one per column.
+    /// Columns without an initial plug are `None`.
+    pub fn curated_roll(&self) -> Vec<Option<usize>> {
+        self.columns.iter().map(|c| c.initial_item_index).collect()
+    }
+
+    /// Returns how many distinct rolls the weapon can currently drop with.
+    pub fn combination_count(&self) -> u64 {
+        self.combinations().total()
+    }
+
+    /// Iterates over every roll the weapon can currently drop with, as plug item indices per column.
+    /// Columns where nothing can roll any more keep their initial plug.
+    pub fn combinations(&self) -> RollCombinations {
+        let pools = self
+            .columns
+            .iter()
+            .map(|c| {
+                let rollable: Vec<usize> = c.rollable().map(|p| p.item_index).collect();
+                if rollable.is_empty() {
+                    c.initial_item_index.into_iter().collect()
+                } else {
+                    rollable
+                }
+            })
+            .collect();
+
+        RollCombinations::new(pools)
+    }
+}
+
+impl InventoryItem {
+    pub fn weapon_rolls(&self) -> Option<WeaponRolls> {
+        WeaponRolls::new(self)
+    }
+}
+
+/// Cartesian product over the perk pools of every column.
+pub struct RollCombinations {
+    pools: Vec<Vec<usize>>,
+    cursor: Vec<usize>,
+    done: bool,
+}
+
+impl RollCombinations {
+    /// Columns with an empty pool are left out, as they don't change the roll.
+    fn new(mut pools: Vec<Vec<usize>>) -> Self {
+        pools.retain(|p| !p.is_empty());
+        Self {
+            cursor: vec![0; pools.len()],
+            done: pools.is_empty(),
+            pools,
+        }
+    }
+
+    /// How many rolls there are in total, including ones that have been iterated over already.
+    pub fn total(&self) -> u64 {
+        if self.pools.is_empty() {
+            return 0;
+        }
+        self.pools
+            .iter()
+            .fold(1u64, |acc, p| acc.saturating_mul(p.len() as u64))
+    }
+}
+
+impl Iterator for RollCombinations {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let roll = self
+            .cursor
+            .iter()
+            .zip(&self.pools)
+            .map(|(i, pool)| pool[*i])
+            .collect();
+
+        // Advance like an odometer, last column first
+        self.done = true;
+        for (i, pool) in self.cursor.iter_mut().zip(&self.pools).rev() {
+            *i += 1;
+            if *i < pool.len() {
+                self.done = false;
+                break;
+            }
+            *i = 0;
+        }
+
+        Some(roll)
+    }
+}
+
+fn perk_column(socket_index: usize, socket: &S808077C3) -> Option<PerkColumn> {
+    let items = investment_manager().items();
+    let plugs = investment_manager().plugs();
+
+    let randomized = usize::try_from(socket.randomized_plugset_index).ok();
+    let mut pool: Vec<RollPlug> = Vec::new();
+    let mut push = |item_index: usize, can_roll: bool| {
+        if let Some(existing) = pool.iter_mut().find(|p| p.item_index == item_index) {
+            existing.can_roll |= can_roll;
+        } else if let Some(item) = items.get_item_by_index(item_index) {
+            pool.push(RollPlug {
+                item_index,
+                item,
+                can_roll,
+            });
+        }
+    };
+
+    if let Some(set) = randomized.and_then(|i| plugs.get_plug_set_by_index(i)) {
+        for entry in &set.entries {
+            if let Ok(item_index) = usize::try_from(entry.item_index) {
+                push(item_index, entry.currently_can_roll != 0);
+            }
+        }
+    } else {
+        for item_index in usize::try_from(socket.reusable_plugset_index)
+            .map(|i| plugs.plug_set_items(i))
+            .unwrap_or_default()
+            .into_iter()
+            .chain(socket_plugs(socket))
+        {
+            push(item_index, true);
+        }
+    }
+
+    if pool.is_empty() || !pool.iter().all(|p| is_perk(&p.item)) {
+        return None;
+    }
+
+    Some(PerkColumn {
+        socket_index,
+        randomized: randomized.is_some(),
+        initial_item_index: usize::try_from(socket.single_initial_item_index).ok(),
+        pool,
+    })
+}
+
+/// Returns true for plugs that are weapon perks, rather than mods, masterworks or cosmetics.
+fn is_perk(plug: &InventoryItem) -> bool {
+    plug.plug_category_identifier()
+        .is_some_and(|identifier| is_perk_category(&identifier))
+}
+
+fn is_perk_category(identifier: &str) -> bool {
+    CosmeticKind::from_plug_category(identifier).is_none()
+        && !["mod", "masterwork", "tracker", "memento", "crafting"]
+            .iter()
+            .any(|s| identifier.contains(s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rolls(pools: &[&[usize]]) -> Vec<Vec<usize>> {
+        RollCombinations::new(pools.iter().map(|p| p.to_vec()).collect()).collect()
+    }
+
+    fn total(pools: &[&[usize]]) -> u64 {
+        RollCombinations::new(pools.iter().map(|p| p.to_vec()).collect()).total()
+    }
+
+    #[test]
+    fn every_combination_once() {
+        let pools: &[&[usize]] = &[&[1, 2], &[3], &[4, 5, 6]];
+        assert_eq!(total(pools), 6);
+        assert_eq!(
+            rolls(pools),
+            [
+                [1, 3, 4],
+                [1, 3, 5],
+                [1, 3, 6],
+                [2, 3, 4],
+                [2, 3, 5],
+                [2, 3, 6]
+            ]
+        );
+    }
+
+    #[test]
+    fn single_socket() {
+        assert_eq!(total(&[&[7, 8, 9]]), 3);
+        assert_eq!(rolls(&[&[7, 8, 9]]), [[7], [8], [9]]);
+    }
+
+    #[test]
+    fn empty_sockets_are_left_out() {
+        assert_eq!(total(&[&[1, 2], &[], &[3]]), 2);
+        assert_eq!(rolls(&[&[1, 2], &[], &[3]]), [[1, 3], [2, 3]]);
+    }
+
+    #[test]
+    fn nothing_to_roll() {
+        assert_eq!(total(&[]), 0);
+        assert!(rolls(&[]).is_empty());
+        assert_eq!(total(&[&[], &[]]), 0);
+        assert!(rolls(&[&[], &[]]).is_empty());
+    }
+
+    #[test]
+    fn total_matches_the_iterator() {
+        let pools: &[&[usize]] = &[&[1, 2, 3], &[4, 5], &[6, 7], &[8]];
+        assert_eq!(rolls(pools).len() as u64, total(pools));
+    }
+
+    #[test]
+    fn perk_categories() {
+        assert!(is_perk_category("barrels"));
+        assert!(is_perk_category("frames"));
+        assert!(!is_perk_category("shader"));
+        assert!(!is_perk_category("weapon_skins_hand_cannon"));
+        assert!(!is_perk_category("v400.weapon.mod_guns"));
+        assert!(!is_perk_category("v460.plugs.weapons.masterworks"));
+        assert!(!is_perk_category("crafting.recipes.empty_socket"));
+    }
+}
@@ -0,0 +1,83 @@
+use tiger_parse::{Pointer, tiger_tag};
+
+use crate::{
+    data::{item::InventoryItem, text::IndexedString},
+    global_instance::investment_manager,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, strum::Display)]
+pub enum ItemSetKind {
+    /// An equipable set with set bonuses, like a raid armor set.
+    Armor,
+    /// A set defined by an item's set data that isn't a quest line, like an ornament set.
+    Collection,
+    /// Weapons sharing a name and category, like the normal and adept versions of a weapon.
+    WeaponFamily,
+}
+
+#[derive(Clone)]
+pub struct ItemSet {
+    pub kind: ItemSetKind,
+    pub name: Option<String>,
+    /// Indices of the member items in the item table.
+    pub item_indices: Vec<usize>,
+    pub perks: Vec<ItemSetPerk>,
+}
+
+/// A perk that becomes active once enough pieces of a set are equipped.
+#[derive(Clone)]
+pub struct ItemSetPerk {
+    pub required_count: u32,
+    pub sandbox_perk_index: i16,
+}
+
+impl ItemSet {
+    pub fn items(&self) -> Vec<InventoryItem> {
+        let items = investment_manager().items();
+        self.item_indices
+            .iter()
+            .filter_map(|i| items.get_item_by_index(*i))
+            .collect()
+    }
+}
+
+impl InventoryItem {
+    /// Returns every set this item is a member of.
+    pub fn item_sets(&self) -> Vec<ItemSet> {
+        let Some(index) = investment_manager().items().index_of_hash(self.data.hash) else {
+            return Vec::new();
+        };
+        investment_manager().item_sets().sets_for_item(index)
+    }
+}
+
+#[tiger_tag(id = 0x80805BC0, size = 0x18)]
+pub struct SEquipableItemSetList {
+    pub file_size: u64,
+    pub sets: Vec<S80805BC4>,
+}
+
+#[derive(Clone)]
+#[tiger_tag(id = 0x80805BC4, size = 0x10)]
+pub struct S80805BC4 {
+    pub hash: u32,
+    #[tag(offset = 0x8)]
+    pub set: Pointer<SEquipableItemSet>,
+}
+
+#[derive(Clone)]
+#[tiger_tag(id = 0x80805BC6, size = 0x30)]
+pub struct SEquipableItemSet {
+    pub hash: u32,
+    pub name: IndexedString,
+    #[tag(offset = 0x10)]
+    pub set_items: Vec<i16>,
+    pub set_perks: Vec<S80805BC9>,
+}
+
+#[derive(Clone)]
+#[tiger_tag(id = 0x80805BC9, size = 0x8)]
+pub struct S80805BC9 {
+    pub required_count: u32,
+    pub sandbox_perk_index: i16,
+}
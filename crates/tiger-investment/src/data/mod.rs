@@ -1,6 +1,15 @@
 pub mod achievement;
 pub mod activity;
+pub mod category;
+pub mod character;
 pub mod credits;
+pub mod destination;
 pub mod image;
 pub mod item;
+pub mod objective;
+pub mod plug;
+pub mod progression;
+pub mod season;
+pub mod stat;
 pub mod text;
+pub mod unlock;
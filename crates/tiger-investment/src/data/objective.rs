@@ -0,0 +1,46 @@
+use tiger_parse::{Pointer, tiger_tag};
+
+use crate::{
+    data::{
+        text::IndexedString,
+        unlock::{Unlock, UnlockKind},
+    },
+    global_instance::investment_manager,
+};
+
+impl SObjective {
+    /// Resolves the unlock value the objective tracks its progress with.
+    pub fn unlock_value(&self) -> Option<Unlock> {
+        investment_manager().unlocks().resolve(
+            UnlockKind::Value,
+            usize::try_from(self.unlock_value_index).ok()?,
+        )
+    }
+}
+
+#[tiger_tag(id = 0x80804B6E, size = 0x18)]
+pub struct SObjectiveList {
+    pub file_size: u64,
+    pub objectives: Vec<S80804B72>,
+}
+
+#[derive(Clone)]
+#[tiger_tag(id = 0x80804B72, size = 0x10)]
+pub struct S80804B72 {
+    pub hash: u32,
+    #[tag(offset = 0x8)]
+    pub objective: Pointer<SObjective>,
+}
+
+#[derive(Clone)]
+#[tiger_tag(id = 0x80804B75, size = 0x48)]
+pub struct SObjective {
+    pub hash: u32,
+    pub completion_value: i32,
+    pub unlock_value_index: i16,
+    #[tag(offset = 0xc)]
+    pub progress_description: IndexedString,
+    pub icon_index: i16,
+    #[tag(offset = 0x18)]
+    pub unk18: Vec<()>,
+}
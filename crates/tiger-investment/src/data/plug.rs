@@ -0,0 +1,116 @@
+use tiger_parse::{NullString, Pointer, tiger_tag};
+
+use crate::{data::item::InventoryItem, global_instance::investment_manager};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, strum::Display)]
+pub enum CosmeticKind {
+    Ornament,
+    Shader,
+}
+
+impl CosmeticKind {
+    /// Classifies a plug by its plug category identifier, e.g. "shader" or "armor_skins_titan_head".
+    pub fn from_plug_category(identifier: &str) -> Option<Self> {
+        if identifier == "shader" {
+            Some(Self::Shader)
+        } else if identifier.contains("skins") {
+            Some(Self::Ornament)
+        } else {
+            None
+        }
+    }
+}
+
+impl InventoryItem {
+    /// Returns the plug category identifier of the item, if it can be plugged into a socket.
+    pub fn plug_category_identifier(&self) -> Option<String> {
+        let plug = self.data.plug.0.as_ref()?;
+        Some(plug.plug_category_identifier.to_string())
+    }
+
+    /// Returns whether this item is an ornament or a shader.
+    pub fn cosmetic_kind(&self) -> Option<CosmeticKind> {
+        CosmeticKind::from_plug_category(&self.plug_category_identifier()?)
+    }
+
+    /// Returns the ornaments that can be applied to this item.
+    pub fn ornaments(&self) -> Vec<InventoryItem> {
+        self.cosmetics(CosmeticKind::Ornament)
+    }
+
+    /// Returns the shaders that can be applied to this item.
+    pub fn shaders(&self) -> Vec<InventoryItem> {
+        self.cosmetics(CosmeticKind::Shader)
+    }
+
+    fn cosmetics(&self, kind: CosmeticKind) -> Vec<InventoryItem> {
+        let items = investment_manager().items();
+        let Some(index) = items.index_of_hash(self.data.hash) else {
+            return Vec::new();
+        };
+        investment_manager()
+            .plugs()
+            .cosmetics_for_item(index, kind)
+            .into_iter()
+            .filter_map(|i| items.get_item_by_index(i))
+            .collect()
+    }
+
+    /// Returns the items this plug can be applied to, for ornaments and shaders.
+    pub fn applies_to(&self) -> Vec<InventoryItem> {
+        let items = investment_manager().items();
+        let Some(index) = items.index_of_hash(self.data.hash) else {
+            return Vec::new();
+        };
+        investment_manager()
+            .plugs()
+            .items_for_plug(index)
+            .into_iter()
+            .filter_map(|i| items.get_item_by_index(i))
+            .collect()
+    }
+}
+
+#[tiger_tag(id = 0x80805BD0, size = 0x18)]
+pub struct SPlugSetList {
+    pub file_size: u64,
+    pub plug_sets: Vec<S80805BD4>,
+}
+
+#[derive(Clone)]
+#[tiger_tag(id = 0x80805BD4, size = 0x10)]
+pub struct S80805BD4 {
+    pub hash: u32,
+    #[tag(offset = 0x8)]
+    pub plug_set: Pointer<SPlugSet>,
+}
+
+#[derive(Clone)]
+#[tiger_tag(id = 0x80805BD6, size = 0x18)]
+pub struct SPlugSet {
+    pub hash: u32,
+    pub is_fake_plug_set: u8,
+    #[tag(offset = 0x8)]
+    pub entries: Vec<S80805BD9>,
+}
+
+#[derive(Clone)]
+#[tiger_tag(id = 0x80805BD9, size = 0x8)]
+pub struct S80805BD9 {
+    pub item_index: i16,
+    /// Whether this plug can still drop as a random roll
+    pub currently_can_roll: u8,
+    #[tag(offset = 0x4)]
+    pub weight: f32,
+}
+
+/// Plug data of an item that can be inserted into a socket.
+#[derive(Clone)]
+#[tiger_tag(id = 0x80807A0C, size = 0x20)]
+pub struct S80807A0C {
+    pub plug_category_hash: u32,
+    #[tag(offset = 0x8)]
+    pub plug_category_identifier: Pointer<NullString>,
+    pub unk10: u64,
+    pub unk18: u64,
+}
@@ -0,0 +1,143 @@
+use tiger_parse::{Pointer, tiger_tag};
+
+use crate::{
+    data::{item::InventoryItem, text::IndexedString},
+    global_instance::investment_manager,
+};
+
+#[derive(Clone)]
+pub struct Progression {
+    pub data: SProgressionData,
+    pub display: SProgressionDisplay,
+}
+
+/// A single rank of a progression, with the reputation needed to reach the next one.
+#[derive(Clone)]
+pub struct ProgressionStep {
+    pub name: Option<String>,
+    pub icon_index: i16,
+    /// Progress needed to go from this step to the next one.
+    pub progress_total: i32,
+    /// Progress needed to reach this step, starting from the first one.
+    pub cumulative_total: i32,
+    pub rewards: Vec<ProgressionReward>,
+}
+
+#[derive(Clone)]
+pub struct ProgressionReward {
+    pub item: InventoryItem,
+    pub quantity: i32,
+}
+
+impl Progression {
+    pub fn new(data: SProgressionData, display: SProgressionDisplay) -> Self {
+        Self { data, display }
+    }
+
+    /// Returns every step of the progression in order, with its display data and rewards.
+    pub fn steps(&self) -> Vec<ProgressionStep> {
+        let items = investment_manager().items();
+
+        let mut cumulative_total = 0;
+        let mut steps = Vec::with_capacity(self.data.steps.len());
+        for (i, step) in self.data.steps.iter().enumerate() {
+            let display = self.display.steps.get(i);
+            let rewards = self
+                .data
+                .reward_items
+                .iter()
+                .filter(|r| r.step_index as usize == i)
+                .filter_map(|r| {
+                    Some(ProgressionReward {
+                        item: items.get_item_by_index(usize::try_from(r.item_index).ok()?)?,
+                        quantity: r.quantity,
+                    })
+                })
+                .collect();
+
+            steps.push(ProgressionStep {
+                name: display.and_then(|d| d.step_name.get()),
+                icon_index: display.map(|d| d.icon_index).unwrap_or(-1),
+                progress_total: step.progress_total,
+                cumulative_total,
+                rewards,
+            });
+            cumulative_total += step.progress_total;
+        }
+        steps
+    }
+}
+
+#[tiger_tag(id = 0x80805AA3, size = 0x18)]
+pub struct SProgressionList {
+    pub file_size: u64,
+    pub progressions: Vec<S80805AA7>,
+}
+
+#[derive(Clone)]
+#[tiger_tag(id = 0x80805AA7, size = 0x10)]
+pub struct S80805AA7 {
+    pub hash: u32,
+    #[tag(offset = 0x8)]
+    pub progression: Pointer<SProgressionData>,
+}
+
+#[derive(Clone)]
+#[tiger_tag(id = 0x80805AA9, size = 0x38)]
+pub struct SProgressionData {
+    pub hash: u32,
+    pub scope: u8,
+    pub repeat_last_step: u8,
+    #[tag(offset = 0x8)]
+    pub steps: Vec<S80805AAD>,
+    pub reward_items: Vec<S80805AAF>,
+    pub visible: u8,
+}
+
+#[derive(Clone)]
+#[tiger_tag(id = 0x80805AAD, size = 0x8)]
+pub struct S80805AAD {
+    pub progress_total: i32,
+    pub unk4: u32,
+}
+
+#[derive(Clone)]
+#[tiger_tag(id = 0x80805AAF, size = 0x10)]
+pub struct S80805AAF {
+    pub step_index: i16,
+    pub item_index: i16,
+    pub quantity: i32,
+    pub ui_display_style: u32,
+}
+
+#[tiger_tag(id = 0x80805A9A, size = 0x18)]
+pub struct SProgressionDisplayList {
+    pub file_size: u64,
+    pub progressions: Vec<S80805A9E>,
+}
+
+#[derive(Clone)]
+#[tiger_tag(id = 0x80805A9E, size = 0x10)]
+pub struct S80805A9E {
+    pub hash: u32,
+    #[tag(offset = 0x8)]
+    pub progression: Pointer<SProgressionDisplay>,
+}
+
+#[derive(Clone)]
+#[tiger_tag(id = 0x80805AA0, size = 0x38)]
+pub struct SProgressionDisplay {
+    pub icon_index: i16,
+    #[tag(offset = 0x4)]
+    pub name: IndexedString,
+    pub description: IndexedString,
+    #[tag(offset = 0x18)]
+    pub steps: Vec<S80805AA2>,
+}
+
+#[derive(Clone)]
+#[tiger_tag(id = 0x80805AA2, size = 0x10)]
+pub struct S80805AA2 {
+    pub step_name: IndexedString,
+    pub icon_index: i16,
+}
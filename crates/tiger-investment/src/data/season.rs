@@ -0,0 +1,154 @@
+use tiger_parse::{Pointer, tiger_tag};
+
+use crate::{
+    data::{item::InventoryItem, progression::Progression, text::IndexedString},
+    global_instance::investment_manager,
+};
+
+#[derive(Clone)]
+pub struct Season {
+    pub data: SSeason,
+}
+
+#[derive(Clone)]
+pub struct SeasonPass {
+    pub data: SSeasonPass,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, strum::Display, strum::EnumIter)]
+pub enum SeasonPassTrack {
+    Free,
+    Premium,
+}
+
+#[derive(Clone)]
+pub struct SeasonPassReward {
+    pub rank: u16,
+    pub track: SeasonPassTrack,
+    pub item_index: usize,
+    pub item: InventoryItem,
+    pub quantity: i32,
+}
+
+impl Season {
+    pub fn new(data: SSeason) -> Self {
+        Self { data }
+    }
+
+    pub fn artifact(&self) -> Option<InventoryItem> {
+        investment_manager()
+            .items()
+            .get_item_by_index(usize::try_from(self.data.artifact_item_index).ok()?)
+    }
+
+    pub fn season_passes(&self) -> Vec<SeasonPass> {
+        let seasons = investment_manager().seasons();
+        self.data
+            .season_pass_indices
+            .iter()
+            .filter_map(|i| seasons.get_season_pass_by_index(usize::try_from(*i).ok()?))
+            .collect()
+    }
+}
+
+impl SeasonPass {
+    pub fn new(data: SSeasonPass) -> Self {
+        Self { data }
+    }
+
+    /// The progression used for the ranks of this season pass.
+    pub fn reward_progression(&self) -> Option<Progression> {
+        investment_manager()
+            .progressions()
+            .get_progression_by_index(usize::try_from(self.data.reward_progression_index).ok()?)
+    }
+
+    /// Returns the rewards of this season pass, ordered by rank.
+    pub fn rewards(&self) -> Vec<SeasonPassReward> {
+        let items = investment_manager().items();
+        let mut rewards: Vec<SeasonPassReward> = self
+            .data
+            .rewards
+            .iter()
+            .filter_map(|r| {
+                let item_index = usize::try_from(r.item_index).ok()?;
+                Some(SeasonPassReward {
+                    rank: r.rank,
+                    track: if r.premium != 0 {
+                        SeasonPassTrack::Premium
+                    } else {
+                        SeasonPassTrack::Free
+                    },
+                    item_index,
+                    item: items.get_item_by_index(item_index)?,
+                    quantity: r.quantity,
+                })
+            })
+            .collect();
+        rewards.sort_by_key(|r| r.rank);
+        rewards
+    }
+}
+
+#[tiger_tag(id = 0x80805B2C, size = 0x18)]
+pub struct SSeasonList {
+    pub file_size: u64,
+    pub seasons: Vec<S80805B30>,
+}
+
+#[derive(Clone)]
+#[tiger_tag(id = 0x80805B30, size = 0x10)]
+pub struct S80805B30 {
+    pub hash: u32,
+    #[tag(offset = 0x8)]
+    pub season: Pointer<SSeason>,
+}
+
+#[derive(Clone)]
+#[tiger_tag(id = 0x80805B32, size = 0x38)]
+pub struct SSeason {
+    pub hash: u32,
+    pub season_number: i32,
+    pub name: IndexedString,
+    pub description: IndexedString,
+    pub icon_index: i16,
+    pub artifact_item_index: i16,
+    pub seal_presentation_node_hash: u32,
+    /// Markers the game uses to decide when the season starts and ends.
+    pub start_marker: u32,
+    pub end_marker: u32,
+    pub season_pass_indices: Vec<i16>,
+}
+
+#[tiger_tag(id = 0x80805B36, size = 0x18)]
+pub struct SSeasonPassList {
+    pub file_size: u64,
+    pub season_passes: Vec<S80805B3A>,
+}
+
+#[derive(Clone)]
+#[tiger_tag(id = 0x80805B3A, size = 0x10)]
+pub struct S80805B3A {
+    pub hash: u32,
+    #[tag(offset = 0x8)]
+    pub season_pass: Pointer<SSeasonPass>,
+}
+
+#[derive(Clone)]
+#[tiger_tag(id = 0x80805B3C, size = 0x20)]
+pub struct SSeasonPass {
+    pub hash: u32,
+    pub name: IndexedString,
+    pub reward_progression_index: i16,
+    pub prestige_progression_index: i16,
+    pub rewards: Vec<S80805B3E>,
+}
+
+#[derive(Clone)]
+#[tiger_tag(id = 0x80805B3E, size = 0xC)]
+pub struct S80805B3E {
+    pub rank: u16,
+    pub item_index: i16,
+    pub quantity: i32,
+    pub premium: u8,
+}
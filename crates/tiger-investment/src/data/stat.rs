@@ -0,0 +1,245 @@
+use rustc_hash::FxHashMap;
+use tiger_parse::{Pointer, tiger_tag};
+
+use crate::{data::item::InventoryItem, global_instance::investment_manager};
+
+/// Maximum value of stats that aren't scaled by a stat group.
+pub const DEFAULT_MAXIMUM_VALUE: i32 = 100;
+
+/// A stat as it's shown in game, after going through the item's stat group.
+#[derive(Clone, Debug)]
+pub struct DisplayedStat {
+    pub stat_type: i32,
+    pub investment_value: i32,
+    pub display_value: i32,
+    pub maximum_value: i32,
+    /// Shown as a number instead of a bar
+    pub display_as_numeric: bool,
+}
+
+impl SScaledStat {
+    /// Converts an investment value to the value displayed in game, by interpolating between the points of the display curve.
+    /// The displayed value is capped at the stat's maximum.
+    pub fn interpolate(&self, investment_value: i32) -> i32 {
+        interpolate_curve(&self.display_interpolation, investment_value).min(self.maximum_value)
+    }
+}
+
+fn interpolate_curve(points: &[S80805BEC], value: i32) -> i32 {
+    let (Some(first), Some(last)) = (points.first(), points.last()) else {
+        return value;
+    };
+
+    if value <= first.value {
+        return first.weight;
+    }
+    if value >= last.value {
+        return last.weight;
+    }
+
+    for pair in points.windows(2) {
+        let (start, end) = (&pair[0], &pair[1]);
+        if value >= start.value && value <= end.value {
+            if end.value == start.value {
+                return end.weight;
+            }
+            let t = (value - start.value) as f64 / (end.value - start.value) as f64;
+            let weight = start.weight as f64 + t * (end.weight - start.weight) as f64;
+            return round_half_even(weight) as i32;
+        }
+    }
+
+    last.weight
+}
+
+/// The game rounds displayed stats to the nearest even number on ties
+fn round_half_even(value: f64) -> f64 {
+    let rounded = value.round();
+    if (value - value.trunc()).abs() == 0.5 && rounded % 2.0 != 0.0 {
+        rounded - value.signum()
+    } else {
+        rounded
+    }
+}
+
+impl InventoryItem {
+    /// Returns the stat group the item's displayed stats are calculated with.
+    pub fn stat_group(&self) -> Option<SStatGroup> {
+        let stats_perks = self.data.stats_perks.0.as_ref()?;
+        investment_manager()
+            .stat_groups()
+            .get_by_index(usize::try_from(stats_perks.stat_group_index).ok()?)
+    }
+
+    /// Sums the investment stats of the item with the investment stats of the plugs at `plug_indices`.
+    pub fn investment_stats(&self, plug_indices: &[usize]) -> Vec<(i32, i32)> {
+        let items = investment_manager().items();
+        let mut order = Vec::new();
+        let mut totals: FxHashMap<i32, i32> = FxHashMap::default();
+
+        let plugs: Vec<InventoryItem> = plug_indices
+            .iter()
+            .filter_map(|i| items.get_item_by_index(*i))
+            .collect();
+        for stats_perks in std::iter::once(self)
+            .chain(plugs.iter())
+            .filter_map(|i| i.data.stats_perks.0.as_ref())
+        {
+            for stat in &stats_perks.stats {
+                let total = totals.entry(stat.stat_type).or_insert_with(|| {
+                    order.push(stat.stat_type);
+                    0
+                });
+                *total += stat.stat_value;
+            }
+        }
+
+        order.into_iter().map(|t| (t, totals[&t])).collect()
+    }
+
+    /// Returns the stats shown in game, including the effect of the plugs at `plug_indices`.
+    /// Items without a stat group show their investment stats as is.
+    pub fn displayed_stats(&self, plug_indices: &[usize]) -> Vec<DisplayedStat> {
+        let investment = self.investment_stats(plug_indices);
+        let Some(group) = self.stat_group() else {
+            return investment
+                .into_iter()
+                .map(|(stat_type, value)| DisplayedStat {
+                    stat_type,
+                    investment_value: value,
+                    display_value: value,
+                    maximum_value: DEFAULT_MAXIMUM_VALUE,
+                    display_as_numeric: true,
+                })
+                .collect();
+        };
+
+        group
+            .scaled_stats
+            .iter()
+            .map(|scaled| {
+                let investment_value = investment
+                    .iter()
+                    .find(|(t, _)| *t == scaled.stat_type)
+                    .map(|(_, v)| *v)
+                    .unwrap_or_default();
+                DisplayedStat {
+                    stat_type: scaled.stat_type,
+                    investment_value,
+                    display_value: scaled.interpolate(investment_value),
+                    maximum_value: scaled.maximum_value,
+                    display_as_numeric: scaled.display_as_numeric != 0,
+                }
+            })
+            .collect()
+    }
+}
+
+#[tiger_tag(id = 0x80805BE0, size = 0x18)]
+pub struct SStatGroupList {
+    pub file_size: u64,
+    pub stat_groups: Vec<S80805BE4>,
+}
+
+#[derive(Clone)]
+#[tiger_tag(id = 0x80805BE4, size = 0x10)]
+pub struct S80805BE4 {
+    pub hash: u32,
+    #[tag(offset = 0x8)]
+    pub stat_group: Pointer<SStatGroup>,
+}
+
+#[derive(Clone)]
+#[tiger_tag(id = 0x80805BE6, size = 0x20)]
+pub struct SStatGroup {
+    pub hash: u32,
+    pub maximum_value: i32,
+    pub ui_position: i32,
+    #[tag(offset = 0x10)]
+    pub scaled_stats: Vec<SScaledStat>,
+}
+
+#[derive(Clone)]
+#[tiger_tag(id = 0x80805BE9, size = 0x20)]
+pub struct SScaledStat {
+    pub stat_type: i32,
+    pub maximum_value: i32,
+    pub display_as_numeric: u8,
+    #[tag(offset = 0x10)]
+    pub display_interpolation: Vec<S80805BEC>,
+}
+
+/// A point on a stat's display curve, mapping an investment value to a displayed value.
+#[derive(Clone)]
+#[tiger_tag(id = 0x80805BEC, size = 0x8)]
+pub struct S80805BEC {
+    pub value: i32,
+    pub weight: i32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn curve(points: &[(i32, i32)]) -> Vec<S80805BEC> {
+        points
+            .iter()
+            .map(|&(value, weight)| S80805BEC { value, weight })
+            .collect()
+    }
+
+    #[test]
+    fn interpolates_between_points() {
+        let points = curve(&[(0, 10), (50, 60), (100, 80)]);
+        assert_eq!(interpolate_curve(&points, -5), 10);
+        assert_eq!(interpolate_curve(&points, 0), 10);
+        assert_eq!(interpolate_curve(&points, 25), 35);
+        assert_eq!(interpolate_curve(&points, 50), 60);
+        assert_eq!(interpolate_curve(&points, 75), 70);
+        assert_eq!(interpolate_curve(&points, 150), 80);
+    }
+
+    #[test]
+    fn repeated_points() {
+        let points = curve(&[(0, 0), (50, 40), (50, 60), (100, 100)]);
+        assert_eq!(interpolate_curve(&points, 50), 40);
+        assert_eq!(interpolate_curve(&points, 60), 68);
+    }
+
+    #[test]
+    fn ties_round_to_even() {
+        let points = curve(&[(0, 0), (2, 1), (4, 4)]);
+        assert_eq!(interpolate_curve(&points, 1), 0);
+        assert_eq!(interpolate_curve(&points, 3), 2);
+    }
+
+    #[test]
+    fn no_curve_shows_the_investment_value() {
+        assert_eq!(interpolate_curve(&[], 42), 42);
+    }
+
+    #[test]
+    fn caps_the_displayed_value() {
+        let stat = SScaledStat {
+            stat_type: 0,
+            maximum_value: 70,
+            display_as_numeric: 0,
+            display_interpolation: curve(&[(0, 0), (200, 140)]),
+        };
+        assert_eq!(stat.interpolate(50), 35);
+        assert_eq!(stat.interpolate(100), 70);
+        assert_eq!(stat.interpolate(150), 70);
+    }
+
+    #[test]
+    fn round_half_even_ties() {
+        assert_eq!(round_half_even(0.5), 0.0);
+        assert_eq!(round_half_even(1.5), 2.0);
+        assert_eq!(round_half_even(2.5), 2.0);
+        assert_eq!(round_half_even(-0.5), 0.0);
+        assert_eq!(round_half_even(-1.5), -2.0);
+        assert_eq!(round_half_even(-2.5), -2.0);
+        assert_eq!(round_half_even(2.4), 2.0);
+        assert_eq!(round_half_even(2.6), 3.0);
+    }
+}
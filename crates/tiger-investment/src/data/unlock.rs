@@ -0,0 +1,91 @@
+use tiger_parse::{Pointer, tiger_tag};
+
+use crate::data::text::IndexedString;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, strum::Display)]
+pub enum UnlockKind {
+    /// A boolean unlock, set once something has been done.
+    Flag,
+    /// A counter, used to track progress like objectives.
+    Value,
+}
+
+/// A resolved reference to an unlock flag or value.
+#[derive(Clone, Debug)]
+pub struct Unlock {
+    pub kind: UnlockKind,
+    /// Index of the unlock in its table.
+    pub index: usize,
+    pub hash: u32,
+    pub name: Option<String>,
+}
+
+impl std::fmt::Display for Unlock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.name.as_ref().filter(|n| !n.is_empty()) {
+            Some(name) => write!(f, "{name} ({})", self.hash),
+            None => write!(f, "{} {}", self.kind, self.hash),
+        }
+    }
+}
+
+/// Something that is gated by, or tracked with, an unlock.
+#[derive(Clone, Debug)]
+pub enum UnlockUsage {
+    /// An activity insertion point that is only available with the unlock.
+    InsertionPoint {
+        activity_index: usize,
+        phase_hash: u32,
+    },
+    /// An activity requirement that fails without the unlock.
+    ActivityRequirement { activity_index: usize },
+    /// An objective that tracks its progress with the unlock value.
+    Objective { objective_index: usize },
+    /// An item with an objective that tracks its progress with the unlock value.
+    Item { item_index: usize },
+}
+
+#[tiger_tag(id = 0x80804B00, size = 0x18)]
+pub struct SUnlockFlagList {
+    pub file_size: u64,
+    pub flags: Vec<S80804B04>,
+}
+
+#[derive(Clone)]
+#[tiger_tag(id = 0x80804B04, size = 0x10)]
+pub struct S80804B04 {
+    pub hash: u32,
+    #[tag(offset = 0x8)]
+    pub flag: Pointer<SUnlockFlag>,
+}
+
+#[derive(Clone)]
+#[tiger_tag(id = 0x80804B06, size = 0x18)]
+pub struct SUnlockFlag {
+    pub hash: u32,
+    pub scope: u8,
+    #[tag(offset = 0x8)]
+    pub name: IndexedString,
+    pub description: IndexedString,
+}
+
+#[tiger_tag(id = 0x80804B10, size = 0x18)]
+pub struct SUnlockValueList {
+    pub file_size: u64,
+    pub values: Vec<S80804B14>,
+}
+
+#[derive(Clone)]
+#[tiger_tag(id = 0x80804B14, size = 0x10)]
+pub struct S80804B14 {
+    pub hash: u32,
+    #[tag(offset = 0x8)]
+    pub value: Pointer<SUnlockValue>,
+}
+
+#[derive(Clone)]
+#[tiger_tag(id = 0x80804B16, size = 0x8)]
+pub struct SUnlockValue {
+    pub hash: u32,
+    pub scope: u8,
+}
@@ -0,0 +1,300 @@
+use crate::{
+    InvestmentData, data::item::InventoryItem, global_instance::investment_manager,
+    manager::socket_plugs,
+};
+
+/// One field of two compared entries. A side is `None` if only the other entry has the field.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FieldDiff {
+    /// Group the field is shown under, e.g. "Stats"
+    pub section: &'static str,
+    pub field: String,
+    pub left: Option<String>,
+    pub right: Option<String>,
+}
+
+impl FieldDiff {
+    pub fn is_changed(&self) -> bool {
+        self.left != self.right
+    }
+}
+
+/// Compares two entries field by field, covering their strings, stats, sockets, perks and icons.
+/// Fields are in the order of `left`, followed by the ones only `right` has.
+/// Returns `None` if the entries aren't of the same kind.
+pub fn diff_entries(left: &InvestmentData, right: &InvestmentData) -> Option<Vec<FieldDiff>> {
+    if left.kind() != right.kind() {
+        return None;
+    }
+    Some(diff_fields(fields(left), fields(right)))
+}
+
+/// Pairs up the fields of two entries by section and name.
+fn diff_fields(left: Vec<Field>, mut right_fields: Vec<Field>) -> Vec<FieldDiff> {
+    let mut diffs: Vec<FieldDiff> = left
+        .into_iter()
+        .map(|(section, field, value)| {
+            let other = right_fields
+                .iter()
+                .position(|(s, f, _)| *s == section && *f == field)
+                .map(|i| right_fields.remove(i).2);
+            FieldDiff {
+                section,
+                field,
+                left: Some(value),
+                right: other,
+            }
+        })
+        .collect();
+    diffs.extend(
+        right_fields
+            .into_iter()
+            .map(|(section, field, value)| FieldDiff {
+                section,
+                field,
+                left: None,
+                right: Some(value),
+            }),
+    );
+    diffs
+}
+
+/// Sections in the order they are listed in.
+const SECTIONS: [&str; 6] = ["Strings", "General", "Stats", "Perks", "Sockets", "Icons"];
+
+type Field = (&'static str, String, String);
+
+/// Flattens an entry into `(section, field, value)`, with every field named uniquely.
+fn fields(entry: &InvestmentData) -> Vec<Field> {
+    let mut fields = vec![
+        ("Strings", "name".to_string(), entry.name()),
+        ("General", "hash".to_string(), entry.hash().to_string()),
+    ];
+    if let Some(itype) = entry.itype() {
+        fields.push(("Strings", "type".to_string(), itype));
+    }
+    if let InvestmentData::InventoryItem(item) = entry {
+        item_fields(item, &mut fields);
+    }
+
+    if let Some(icon_index) = entry.icon_index() {
+        fields.push(("Icons", "icon_index".to_string(), icon_index.to_string()));
+        let icon = usize::try_from(icon_index)
+            .ok()
+            .and_then(|i| investment_manager().icons().get_index(i));
+        if let Some(icon) = icon {
+            let mut counts: Vec<(&'static str, usize)> = Vec::new();
+            for (field, tag) in icon.tag_hashes() {
+                let count = match counts.iter_mut().find(|(f, _)| *f == field) {
+                    Some((_, count)) => {
+                        *count += 1;
+                        *count
+                    }
+                    None => {
+                        counts.push((field, 0));
+                        0
+                    }
+                };
+                fields.push(("Icons", format!("{field}[{count}]"), tag.to_string()));
+            }
+        }
+    }
+
+    // Stable, so fields keep their order within a section
+    fields.sort_by_key(|(section, _, _)| SECTIONS.iter().position(|s| s == section));
+    fields
+}
+
+fn item_fields(item: &InventoryItem, fields: &mut Vec<Field>) {
+    for (field, string) in [
+        ("display_source", &item.display.display_source),
+        ("toast", &item.display.toast),
+        ("flavor", &item.display.flavor),
+    ] {
+        fields.push((
+            "Strings",
+            field.to_string(),
+            string.get().unwrap_or_default(),
+        ));
+    }
+    if let Some(tier) = item.tier() {
+        fields.push(("General", "tier".to_string(), tier.to_string()));
+    }
+    if let Some(class) = item.restriction.class {
+        fields.push(("General", "class".to_string(), class.to_string()));
+    }
+    let categories: Vec<String> = item.categories().iter().map(|c| c.display_path()).collect();
+    if !categories.is_empty() {
+        fields.push(("General", "categories".to_string(), categories.join(", ")));
+    }
+
+    if let Some(stats_perks) = &item.data.stats_perks.0 {
+        fields.push((
+            "Stats",
+            "stat_group_index".to_string(),
+            stats_perks.stat_group_index.to_string(),
+        ));
+        for stat in item.displayed_stats(&[]) {
+            fields.push((
+                "Stats",
+                format!("stat {}", stat.stat_type),
+                format!(
+                    "{} (investment {})",
+                    stat.display_value, stat.investment_value
+                ),
+            ));
+        }
+        for (i, perk) in stats_perks.perks.iter().enumerate() {
+            fields.push((
+                "Perks",
+                format!("perk {i}"),
+                format!("sandbox_perk_index {}", perk.sandbox_perk_index),
+            ));
+        }
+    }
+
+    if let Some(sockets) = &item.data.sockets.0 {
+        let plugs = investment_manager().plugs();
+        for (i, socket) in sockets.data.iter().enumerate() {
+            fields.push((
+                "Sockets",
+                format!("socket {i} plugs"),
+                item_names(&socket_plugs(socket)),
+            ));
+            for (field, plug_set_index) in [
+                ("reusable plug set", socket.reusable_plugset_index),
+                ("randomized plug set", socket.randomized_plugset_index),
+            ] {
+                if let Ok(index) = usize::try_from(plug_set_index) {
+                    fields.push((
+                        "Sockets",
+                        format!("socket {i} {field}"),
+                        item_names(&plugs.plug_set_items(index)),
+                    ));
+                }
+            }
+        }
+    }
+}
+
+/// Joins the names of the items at `indices`, falling back to the index for items without one.
+fn item_names(indices: &[usize]) -> String {
+    let items = investment_manager().items();
+    indices
+        .iter()
+        .map(|i| {
+            items
+                .get_item_by_index(*i)
+                .and_then(|item| item.display.name.get())
+                .filter(|name| !name.is_empty())
+                .unwrap_or_else(|| format!("#{i}"))
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::activity::graph::SActivityGraph;
+
+    fn field(section: &'static str, field: &str, value: &str) -> Field {
+        (section, field.to_string(), value.to_string())
+    }
+
+    fn diff(
+        section: &'static str,
+        field: &str,
+        left: Option<&str>,
+        right: Option<&str>,
+    ) -> FieldDiff {
+        FieldDiff {
+            section,
+            field: field.to_string(),
+            left: left.map(str::to_string),
+            right: right.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn identical_entries_have_no_changes() {
+        let graph = || {
+            InvestmentData::ActivityGraph(Box::new(SActivityGraph {
+                file_size: 0,
+                nodes: Vec::new(),
+            }))
+        };
+        let diffs = diff_entries(&graph(), &graph()).unwrap();
+        assert!(!diffs.is_empty());
+        assert!(diffs.iter().all(|d| !d.is_changed()));
+    }
+
+    #[test]
+    fn entries_of_different_kinds_are_not_compared() {
+        let graph = InvestmentData::ActivityGraph(Box::new(SActivityGraph {
+            file_size: 0,
+            nodes: Vec::new(),
+        }));
+        assert_eq!(diff_entries(&InvestmentData::Achievement, &graph), None);
+    }
+
+    #[test]
+    fn changed_fields() {
+        let diffs = diff_fields(
+            vec![
+                field("Strings", "name", "Ace of Spades"),
+                field("General", "hash", "347366834"),
+            ],
+            vec![
+                field("Strings", "name", "Ace of Spades"),
+                field("General", "hash", "1234"),
+            ],
+        );
+        assert_eq!(
+            diffs,
+            [
+                diff(
+                    "Strings",
+                    "name",
+                    Some("Ace of Spades"),
+                    Some("Ace of Spades")
+                ),
+                diff("General", "hash", Some("347366834"), Some("1234")),
+            ]
+        );
+        assert!(!diffs[0].is_changed());
+        assert!(diffs[1].is_changed());
+    }
+
+    #[test]
+    fn fields_only_one_side_has() {
+        let diffs = diff_fields(
+            vec![
+                field("Stats", "stat 1", "10"),
+                field("Perks", "perk 0", "sandbox_perk_index 3"),
+            ],
+            vec![
+                field("Perks", "perk 1", "sandbox_perk_index 4"),
+                field("Stats", "stat 1", "10"),
+            ],
+        );
+        assert_eq!(
+            diffs,
+            [
+                diff("Stats", "stat 1", Some("10"), Some("10")),
+                diff("Perks", "perk 0", Some("sandbox_perk_index 3"), None),
+                diff("Perks", "perk 1", None, Some("sandbox_perk_index 4")),
+            ]
+        );
+    }
+
+    #[test]
+    fn fields_are_matched_within_their_section() {
+        let diffs = diff_fields(
+            vec![field("Strings", "type", "Hand Cannon")],
+            vec![field("General", "type", "Hand Cannon")],
+        );
+        assert_eq!(diffs.len(), 2);
+        assert!(diffs.iter().all(FieldDiff::is_changed));
+    }
+}
@@ -18,22 +18,43 @@ use crate::{
             Activity, SActivityData, SActivityDisplayData, graph::SActivityGraph,
             types::SActivityType,
         },
+        destination::{Destination, Place},
         item::{InventoryItem, SInventoryItem, SInventoryItemDisplay},
+        progression::Progression,
+        season::Season,
+        text::IndexedString,
     },
     global_instance::investment_manager,
 };
 
 pub mod data;
+pub mod diff;
 pub mod global_instance;
 pub mod manager;
+pub mod search;
 
-#[derive(Clone, strum::IntoStaticStr)]
+#[derive(Clone, strum::IntoStaticStr, strum::EnumDiscriminants)]
+#[strum_discriminants(
+    name(InvestmentKind),
+    derive(
+        Hash,
+        strum::Display,
+        strum::EnumIter,
+        strum::IntoStaticStr,
+        serde::Serialize,
+        serde::Deserialize
+    )
+)]
 pub enum InvestmentData {
     Achievement,
     Activity(Box<Activity>),
     ActivityGraph(Box<SActivityGraph>),
     ActivityType(Box<SActivityType>),
     InventoryItem(Box<InventoryItem>),
+    Progression(Box<Progression>),
+    Season(Box<Season>),
+    Destination(Box<Destination>),
+    Place(Box<Place>),
 }
 
 impl InvestmentData {
@@ -41,6 +62,10 @@ impl InvestmentData {
         match self {
             Self::Activity(a) => a.display.display_properties.name.get().unwrap_or_default(),
             Self::InventoryItem(i) => i.display.name.get().unwrap_or_default(),
+            Self::Progression(p) => p.display.name.get().unwrap_or_default(),
+            Self::Season(s) => s.data.name.get().unwrap_or_default(),
+            Self::Destination(d) => d.data.name.get().unwrap_or_default(),
+            Self::Place(p) => p.data.name.get().unwrap_or_default(),
             // TODO: default missing name
             _ => String::new(),
         }
@@ -58,9 +83,71 @@ impl InvestmentData {
         match self {
             Self::Activity(a) => a.data.hash,
             Self::InventoryItem(i) => i.data.hash,
+            Self::Progression(p) => p.data.hash,
+            Self::Season(s) => s.data.hash,
+            Self::Destination(d) => d.data.hash,
+            Self::Place(p) => p.data.hash,
             _ => 0,
         }
     }
+
+    pub fn kind(&self) -> InvestmentKind {
+        self.into()
+    }
+
+    /// Returns a lightweight reference to this entry, which can be resolved back with `EntryRef::resolve`.
+    pub fn entry_ref(&self) -> EntryRef {
+        EntryRef {
+            kind: self.kind(),
+            hash: self.hash(),
+        }
+    }
+
+    pub fn icon_index(&self) -> Option<i16> {
+        match self {
+            Self::InventoryItem(i) => Some(i.display.icon_index),
+            Self::Progression(p) => Some(p.display.icon_index),
+            Self::Season(s) => Some(s.data.icon_index),
+            Self::Destination(d) => Some(d.data.icon_index),
+            Self::Place(p) => Some(p.data.icon_index),
+            _ => None,
+        }
+    }
+
+    /// The indexed string the name of this entry is stored as.
+    pub fn name_string(&self) -> Option<IndexedString> {
+        match self {
+            Self::Activity(a) => Some(a.display.display_properties.name.clone()),
+            Self::InventoryItem(i) => Some(i.display.name.clone()),
+            Self::Progression(p) => Some(p.display.name.clone()),
+            Self::Season(s) => Some(s.data.name.clone()),
+            Self::Destination(d) => Some(d.data.name.clone()),
+            Self::Place(p) => Some(p.data.name.clone()),
+            _ => None,
+        }
+    }
+
+    /// Hash of the destination this entry takes place at, if any.
+    pub fn destination_hash(&self) -> Option<u32> {
+        match self {
+            Self::Activity(a) => a.destination().map(|d| d.data.hash),
+            Self::Destination(d) => Some(d.data.hash),
+            _ => None,
+        }
+    }
+}
+
+/// Points to an entry by its kind and hash, without holding on to its data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct EntryRef {
+    pub kind: InvestmentKind,
+    pub hash: u32,
+}
+
+impl EntryRef {
+    pub fn resolve(&self) -> Option<InvestmentData> {
+        investment_manager().get_entry(*self)
+    }
 }
 
 #[derive(Default)]
@@ -100,6 +187,10 @@ impl<K: Eq + Hash, V> IndexableHashMap<K, V> {
         self.values.get(*index)
     }
 
+    pub fn index_of(&self, hash: &K) -> Option<usize> {
+        self.hash_to_index.get(hash).copied()
+    }
+
     pub fn insert(&mut self, k: K, v: V) -> Option<&V> {
         self.values.push(v);
         self.hash_to_index.insert(k, self.values.len() - 1);
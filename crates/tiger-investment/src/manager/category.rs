@@ -0,0 +1,123 @@
+use std::collections::VecDeque;
+
+use rustc_hash::FxHashSet;
+use tiger_parse::{PackageManagerExt, TigerReadable};
+use tiger_pkg::package_manager;
+
+use crate::{
+    IndexableHashMap,
+    data::category::{ItemCategory, SItemCategoryList},
+};
+
+pub struct CategoryManager {
+    categories: IndexableHashMap<u32, ItemCategory>,
+}
+
+impl CategoryManager {
+    #[tracing::instrument]
+    pub fn new() -> anyhow::Result<Self> {
+        let category_tag: SItemCategoryList = package_manager().read_tag_struct(
+            package_manager().get_all_by_reference(SItemCategoryList::ID.unwrap())[0].0,
+        )?;
+
+        let mut categories = IndexableHashMap::new();
+        for (i, data) in category_tag.categories.iter().enumerate() {
+            categories.insert(data.hash, ItemCategory::new(i, data.category.0.clone()));
+        }
+        Ok(Self { categories })
+    }
+
+    /// Returns a category by its hash.
+    #[tracing::instrument(skip(self))]
+    pub fn get_by_hash(&self, hash: u32) -> Option<ItemCategory> {
+        self.categories.lookup_hash(&hash).cloned()
+    }
+
+    /// Returns a category by its index in the table.
+    #[tracing::instrument(skip(self))]
+    pub fn get_by_index(&self, index: usize) -> Option<ItemCategory> {
+        self.categories.get(index).cloned()
+    }
+
+    pub fn categories(&self) -> &Vec<ItemCategory> {
+        self.categories.values()
+    }
+
+    /// Returns the indices of every category above the category at `index`, closest first.
+    pub fn ancestors(&self, index: usize) -> Vec<usize> {
+        let mut ancestors = Vec::new();
+        let mut visited = FxHashSet::default();
+        let mut queue = VecDeque::from([index]);
+        while let Some(current) = queue.pop_front() {
+            let Some(category) = self.categories.get(current) else {
+                continue;
+            };
+            for parent in &category.data.parent_category_indices {
+                if let Ok(parent) = usize::try_from(*parent)
+                    && visited.insert(parent)
+                {
+                    ancestors.push(parent);
+                    queue.push_back(parent);
+                }
+            }
+        }
+        ancestors
+    }
+
+    /// Returns the chain of categories from a root category down to the category at `index`.
+    /// Follows the first parent of every category when there are multiple.
+    pub fn path(&self, index: usize) -> Vec<ItemCategory> {
+        let mut path = Vec::new();
+        let mut visited = FxHashSet::default();
+        let mut current = Some(index);
+        while let Some(index) = current
+            && visited.insert(index)
+            && let Some(category) = self.categories.get(index)
+        {
+            current = category
+                .data
+                .parent_category_indices
+                .first()
+                .and_then(|i| usize::try_from(*i).ok());
+            path.push(category.clone());
+        }
+        path.reverse();
+        path
+    }
+
+    /// Returns the categories that aren't below any other category.
+    pub fn roots(&self) -> Vec<ItemCategory> {
+        self.categories
+            .values()
+            .iter()
+            .filter(|c| is_root(c))
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the indices of the root categories the category at `index` is below, or itself if it is one.
+    pub fn roots_of(&self, index: usize) -> Vec<usize> {
+        std::iter::once(index)
+            .chain(self.ancestors(index))
+            .filter(|i| self.categories.get(*i).is_some_and(is_root))
+            .collect()
+    }
+
+    /// Returns true if the category at `index` is `ancestor_index`, or is below it.
+    pub fn is_in(&self, index: usize, ancestor_index: usize) -> bool {
+        index == ancestor_index || self.ancestors(index).contains(&ancestor_index)
+    }
+
+    /// Returns the index of a category in the table by its hash.
+    pub fn index_of_hash(&self, hash: u32) -> Option<usize> {
+        self.categories.index_of(&hash)
+    }
+}
+
+fn is_root(category: &ItemCategory) -> bool {
+    !category
+        .data
+        .parent_category_indices
+        .iter()
+        .any(|i| usize::try_from(*i).is_ok())
+}
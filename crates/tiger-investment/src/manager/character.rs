@@ -0,0 +1,71 @@
+use tiger_parse::{PackageManagerExt, TigerReadable};
+use tiger_pkg::package_manager;
+
+use crate::data::character::{
+    DestinyClass, DestinyGender, DestinyRace, SClass, SClassList, SGender, SGenderList, SRace,
+    SRaceList,
+};
+
+/// Holds the class, race and gender definitions.
+pub struct CharacterManager {
+    classes: Vec<SClass>,
+    races: Vec<SRace>,
+    genders: Vec<SGender>,
+}
+
+impl CharacterManager {
+    #[tracing::instrument]
+    pub fn new() -> anyhow::Result<Self> {
+        let class_tag: SClassList = package_manager().read_tag_struct(
+            package_manager().get_all_by_reference(SClassList::ID.unwrap())[0].0,
+        )?;
+        let race_tag: SRaceList = package_manager()
+            .read_tag_struct(package_manager().get_all_by_reference(SRaceList::ID.unwrap())[0].0)?;
+        let gender_tag: SGenderList = package_manager().read_tag_struct(
+            package_manager().get_all_by_reference(SGenderList::ID.unwrap())[0].0,
+        )?;
+
+        Ok(Self {
+            classes: class_tag
+                .classes
+                .iter()
+                .map(|c| c.class.0.clone())
+                .collect(),
+            races: race_tag.races.iter().map(|r| r.race.0.clone()).collect(),
+            genders: gender_tag
+                .genders
+                .iter()
+                .map(|g| g.gender.0.clone())
+                .collect(),
+        })
+    }
+
+    pub fn classes(&self) -> &[SClass] {
+        &self.classes
+    }
+
+    pub fn races(&self) -> &[SRace] {
+        &self.races
+    }
+
+    pub fn genders(&self) -> &[SGender] {
+        &self.genders
+    }
+
+    pub fn class(&self, class: DestinyClass) -> Option<&SClass> {
+        self.classes.iter().find(|c| c.class_type == class as u8)
+    }
+
+    pub fn race(&self, race: DestinyRace) -> Option<&SRace> {
+        self.races.iter().find(|r| r.race_type == race as u8)
+    }
+
+    pub fn gender(&self, gender: DestinyGender) -> Option<&SGender> {
+        self.genders.iter().find(|g| g.gender_type == gender as u8)
+    }
+
+    /// Returns a gender by its index in the table.
+    pub fn gender_by_index(&self, index: usize) -> Option<&SGender> {
+        self.genders.get(index)
+    }
+}
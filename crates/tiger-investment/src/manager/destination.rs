@@ -0,0 +1,109 @@
+use tiger_parse::{PackageManagerExt, TigerReadable};
+use tiger_pkg::package_manager;
+
+use crate::{
+    IndexableHashMap, InvestmentData,
+    data::destination::{Destination, Place, SDestinationList, SPlaceList},
+    manager::search_names,
+    search::{SearchFilter, pipeline::SearchSink},
+};
+
+pub struct DestinationManager {
+    destinations: IndexableHashMap<u32, InvestmentData>,
+    places: IndexableHashMap<u32, InvestmentData>,
+}
+
+impl DestinationManager {
+    #[tracing::instrument]
+    pub fn new() -> anyhow::Result<Self> {
+        let destination_tag: SDestinationList = package_manager().read_tag_struct(
+            package_manager().get_all_by_reference(SDestinationList::ID.unwrap())[0].0,
+        )?;
+        let place_tag: SPlaceList = package_manager().read_tag_struct(
+            package_manager().get_all_by_reference(SPlaceList::ID.unwrap())[0].0,
+        )?;
+
+        let mut destinations = IndexableHashMap::new();
+        for data in destination_tag.destinations.iter() {
+            destinations.insert(
+                data.hash,
+                InvestmentData::Destination(Box::new(Destination::new(data.destination.0.clone()))),
+            );
+        }
+
+        let mut places = IndexableHashMap::new();
+        for data in place_tag.places.iter() {
+            places.insert(
+                data.hash,
+                InvestmentData::Place(Box::new(Place::new(data.place.0.clone()))),
+            );
+        }
+
+        Ok(Self {
+            destinations,
+            places,
+        })
+    }
+
+    /// Sends the destinations and places whose name matches `name`, allowing for typos, with a score for how well it matched.
+    #[tracing::instrument(skip(self))]
+    pub fn search_by_name(&self, sink: SearchSink, name: String, filter: SearchFilter) {
+        search_names(self.destinations.values(), &sink, &name, &filter);
+        search_names(self.places.values(), &sink, &name, &filter);
+    }
+
+    /// Returns a destination or place by its hash.
+    #[tracing::instrument(skip(self))]
+    pub fn get_by_hash(&self, hash: u32) -> Option<InvestmentData> {
+        self.destinations
+            .lookup_hash(&hash)
+            .or_else(|| self.places.lookup_hash(&hash))
+            .cloned()
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn get_destination_by_index(&self, index: usize) -> Option<Destination> {
+        match self.destinations.get(index)? {
+            InvestmentData::Destination(d) => Some(*d.clone()),
+            _ => None,
+        }
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn get_place_by_index(&self, index: usize) -> Option<Place> {
+        match self.places.get(index)? {
+            InvestmentData::Place(p) => Some(*p.clone()),
+            _ => None,
+        }
+    }
+
+    /// Returns every destination, in table order.
+    pub fn destinations(&self) -> Vec<Destination> {
+        self.destinations
+            .values()
+            .iter()
+            .filter_map(|d| match d {
+                InvestmentData::Destination(d) => Some(*d.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns the index of a destination in the table by its hash.
+    pub fn destination_index_of_hash(&self, hash: u32) -> Option<usize> {
+        self.destinations.index_of(&hash)
+    }
+
+    /// Returns the index of a place in the table by its hash.
+    pub fn place_index_of_hash(&self, hash: u32) -> Option<usize> {
+        self.places.index_of(&hash)
+    }
+
+    /// Returns every destination and place, in table order.
+    pub fn values(&self) -> impl Iterator<Item = &InvestmentData> {
+        self.destinations
+            .values()
+            .iter()
+            .chain(self.places.values())
+    }
+}
@@ -0,0 +1,150 @@
+use std::sync::OnceLock;
+
+use rustc_hash::{FxHashMap, FxHashSet};
+use tiger_parse::{PackageManagerExt, TigerReadable};
+use tiger_pkg::package_manager;
+
+use crate::{
+    InvestmentData,
+    data::{
+        category::WEAPON_CATEGORY_HASH,
+        item::set::{ItemSet, ItemSetKind, ItemSetPerk, SEquipableItemSet, SEquipableItemSetList},
+    },
+    global_instance::investment_manager,
+};
+
+pub struct ItemSetManager {
+    equipable_sets: Vec<SEquipableItemSet>,
+    /// Sets need the item names and categories, so they are only built once they are first needed.
+    sets: OnceLock<ItemSets>,
+}
+
+struct ItemSets {
+    sets: Vec<ItemSet>,
+    /// Item index to the indices of the sets it's in
+    by_item: FxHashMap<usize, Vec<usize>>,
+}
+
+impl ItemSetManager {
+    #[tracing::instrument]
+    pub fn new() -> anyhow::Result<Self> {
+        let set_tag: SEquipableItemSetList = package_manager().read_tag_struct(
+            package_manager().get_all_by_reference(SEquipableItemSetList::ID.unwrap())[0].0,
+        )?;
+
+        Ok(Self {
+            equipable_sets: set_tag.sets.iter().map(|s| s.set.0.clone()).collect(),
+            sets: OnceLock::new(),
+        })
+    }
+
+    pub fn sets(&self) -> &[ItemSet] {
+        &self.built().sets
+    }
+
+    /// Returns every set the item at `item_index` is a member of.
+    #[tracing::instrument(skip(self))]
+    pub fn sets_for_item(&self, item_index: usize) -> Vec<ItemSet> {
+        let built = self.built();
+        built
+            .by_item
+            .get(&item_index)
+            .map(|sets| sets.iter().map(|i| built.sets[*i].clone()).collect())
+            .unwrap_or_default()
+    }
+
+    fn built(&self) -> &ItemSets {
+        self.sets.get_or_init(|| self.build())
+    }
+
+    #[tracing::instrument(skip(self))]
+    fn build(&self) -> ItemSets {
+        let mut sets = Vec::new();
+
+        for set in &self.equipable_sets {
+            sets.push(ItemSet {
+                kind: ItemSetKind::Armor,
+                name: set.name.get(),
+                item_indices: set
+                    .set_items
+                    .iter()
+                    .filter_map(|i| usize::try_from(*i).ok())
+                    .collect(),
+                perks: set
+                    .set_perks
+                    .iter()
+                    .map(|p| ItemSetPerk {
+                        required_count: p.required_count,
+                        sandbox_perk_index: p.sandbox_perk_index,
+                    })
+                    .collect(),
+            });
+        }
+
+        let items = investment_manager().items();
+        let mut seen_collections = FxHashSet::default();
+        let mut families: FxHashMap<(String, usize), Vec<usize>> = FxHashMap::default();
+        for (index, data) in items.values().iter().enumerate() {
+            let InvestmentData::InventoryItem(item) = data else {
+                continue;
+            };
+
+            if let Some(set_data) = &item.data.set_data.0
+                && set_data.quest_line_item_index < 0
+            {
+                let members: Vec<usize> = set_data
+                    .items
+                    .iter()
+                    .filter_map(|i| usize::try_from(i.item_index).ok())
+                    .collect();
+                if members.len() > 1 && seen_collections.insert(members.clone()) {
+                    sets.push(ItemSet {
+                        kind: ItemSetKind::Collection,
+                        name: None,
+                        item_indices: members,
+                        perks: Vec::new(),
+                    });
+                }
+            }
+
+            if item.in_category(WEAPON_CATEGORY_HASH)
+                && let Some(name) = item.display.name.get()
+                && let Some(category) = item.primary_category()
+            {
+                families
+                    .entry((family_name(&name), category.index))
+                    .or_default()
+                    .push(index);
+            }
+        }
+
+        for ((name, _), members) in families {
+            if members.len() > 1 {
+                sets.push(ItemSet {
+                    kind: ItemSetKind::WeaponFamily,
+                    name: Some(name),
+                    item_indices: members,
+                    perks: Vec::new(),
+                });
+            }
+        }
+
+        let mut by_item: FxHashMap<usize, Vec<usize>> = FxHashMap::default();
+        for (i, set) in sets.iter().enumerate() {
+            for item_index in &set.item_indices {
+                by_item.entry(*item_index).or_default().push(i);
+            }
+        }
+
+        ItemSets { sets, by_item }
+    }
+}
+
+/// Strips a trailing suffix in parentheses from a weapon name, e.g. "Fatebringer (Timelost)" -> "Fatebringer"
+fn family_name(name: &str) -> String {
+    let name = name.trim();
+    match name.rfind(" (") {
+        Some(i) if name.ends_with(')') => name[..i].to_string(),
+        _ => name.to_string(),
+    }
+}
@@ -1,17 +1,19 @@
-use std::{collections::BTreeMap, sync::Arc};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, OnceLock},
+};
 
-use crossbeam::channel::TrySendError;
 use dashmap::DashMap;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 use strum::IntoEnumIterator;
 use tiger_parse::{PackageManagerExt, TigerReadable};
 use tiger_pkg::{TagHash, package_manager};
 use tiger_text::{Language, LocalizedStrings};
 
 use crate::{
-    IndexableHashMap, InvestmentData,
+    EntryRef, IndexableHashMap, InvestmentData, InvestmentKind,
     data::{
         activity::{
             Activity, SActivityData, SActivityDisplayData, SActivityDisplayList, SActivityList,
@@ -20,13 +22,63 @@ use crate::{
         item::{InventoryItem, SInventoryItem, SInventoryItemDisplayList, SItemList},
         text::SIndexedLocalizedStrings,
     },
+    search::{
+        SearchFilter, TypeFilter,
+        hash::{HashQuery, TAG_MATCH_SCORE},
+        pipeline::{SearchCancelled, SearchSink},
+        query::Query,
+        rank::rank_name,
+        text::{TextIndex, TextQuery},
+    },
 };
 
+mod category;
+mod character;
+mod destination;
+mod item_set;
+mod objective;
+mod plug;
+mod progression;
+mod reference;
+mod season;
+mod stat_group;
+mod tag;
+mod unlock;
+
+pub use category::CategoryManager;
+pub use character::CharacterManager;
+pub use destination::DestinationManager;
+pub use item_set::ItemSetManager;
+pub use objective::ObjectiveManager;
+pub use plug::{PlugManager, socket_plug_sets, socket_plugs};
+pub use progression::ProgressionManager;
+pub use reference::{Reference, ReferenceIndex, ReferenceTarget};
+pub use season::SeasonManager;
+pub use stat_group::StatGroupManager;
+pub use tag::{TagIndex, TagOwner};
+pub use unlock::UnlockManager;
+
 pub struct InvestmentManager {
     activities: Arc<ActivityManager>,
     strings: Arc<StringManager>,
     items: Arc<ItemManager>,
     icons: Arc<IconManager>,
+    objectives: Arc<ObjectiveManager>,
+    progressions: Arc<ProgressionManager>,
+    seasons: Arc<SeasonManager>,
+    destinations: Arc<DestinationManager>,
+    characters: Arc<CharacterManager>,
+    categories: Arc<CategoryManager>,
+    item_sets: Arc<ItemSetManager>,
+    plugs: Arc<PlugManager>,
+    stat_groups: Arc<StatGroupManager>,
+    unlocks: Arc<UnlockManager>,
+    references: OnceLock<Arc<ReferenceIndex>>,
+    tags: OnceLock<Arc<TagIndex>>,
+    /// Rebuilt whenever the language changes.
+    text_index: RwLock<Option<Arc<TextIndex>>>,
+    /// Held while the text index is built, so concurrent searches wait for one build instead of each doing their own.
+    text_index_build: Mutex<()>,
 }
 
 pub struct ActivityManager {
@@ -56,6 +108,20 @@ impl InvestmentManager {
             activities: Arc::new(ActivityManager::new()?),
             items: Arc::new(ItemManager::new()?),
             icons: Arc::new(IconManager::new()?),
+            objectives: Arc::new(ObjectiveManager::new()?),
+            progressions: Arc::new(ProgressionManager::new()?),
+            seasons: Arc::new(SeasonManager::new()?),
+            destinations: Arc::new(DestinationManager::new()?),
+            characters: Arc::new(CharacterManager::new()?),
+            categories: Arc::new(CategoryManager::new()?),
+            item_sets: Arc::new(ItemSetManager::new()?),
+            plugs: Arc::new(PlugManager::new()?),
+            stat_groups: Arc::new(StatGroupManager::new()?),
+            unlocks: Arc::new(UnlockManager::new()?),
+            references: OnceLock::new(),
+            tags: OnceLock::new(),
+            text_index: RwLock::new(None),
+            text_index_build: Mutex::new(()),
         })
     }
 
@@ -75,7 +141,244 @@ impl InvestmentManager {
         self.icons.clone()
     }
 
-    // TODO: search by partial hash (convert to string + contains?)
+    pub fn objectives(&self) -> Arc<ObjectiveManager> {
+        self.objectives.clone()
+    }
+
+    pub fn progressions(&self) -> Arc<ProgressionManager> {
+        self.progressions.clone()
+    }
+
+    pub fn seasons(&self) -> Arc<SeasonManager> {
+        self.seasons.clone()
+    }
+
+    pub fn destinations(&self) -> Arc<DestinationManager> {
+        self.destinations.clone()
+    }
+
+    pub fn characters(&self) -> Arc<CharacterManager> {
+        self.characters.clone()
+    }
+
+    pub fn categories(&self) -> Arc<CategoryManager> {
+        self.categories.clone()
+    }
+
+    pub fn item_sets(&self) -> Arc<ItemSetManager> {
+        self.item_sets.clone()
+    }
+
+    pub fn plugs(&self) -> Arc<PlugManager> {
+        self.plugs.clone()
+    }
+
+    pub fn stat_groups(&self) -> Arc<StatGroupManager> {
+        self.stat_groups.clone()
+    }
+
+    pub fn unlocks(&self) -> Arc<UnlockManager> {
+        self.unlocks.clone()
+    }
+
+    pub fn references(&self) -> Arc<ReferenceIndex> {
+        self.references
+            .get_or_init(|| Arc::new(ReferenceIndex::build()))
+            .clone()
+    }
+
+    pub fn tags(&self) -> Arc<TagIndex> {
+        self.tags
+            .get_or_init(|| Arc::new(TagIndex::build()))
+            .clone()
+    }
+
+    /// Sets the language of all strings, and throws away everything built from the old ones.
+    pub fn set_lang(&self, new_lang: Language) -> anyhow::Result<()> {
+        self.strings.set_lang(new_lang)?;
+        *self.text_index.write() = None;
+        Ok(())
+    }
+
+    /// Returns the full-text index for the current language, building it if needed.
+    /// Fails if `sink`'s search is cancelled while waiting on the build.
+    pub fn text_index(&self, sink: &SearchSink) -> Result<Arc<TextIndex>, SearchCancelled> {
+        if let Some(index) = self.current_text_index() {
+            return Ok(index);
+        }
+
+        let _building = self.text_index_build.lock();
+        // Another search may have built it while this one was waiting
+        if let Some(index) = self.current_text_index() {
+            return Ok(index);
+        }
+        let index = Arc::new(TextIndex::build(sink)?);
+        *self.text_index.write() = Some(index.clone());
+        Ok(index)
+    }
+
+    fn current_text_index(&self) -> Option<Arc<TextIndex>> {
+        self.text_index
+            .read()
+            .as_ref()
+            .filter(|index| index.language() == self.strings.lang())
+            .cloned()
+    }
+
+    /// Searches the descriptions, flavor text and other text fields of every table.
+    #[tracing::instrument(skip(self))]
+    pub fn search_full_text(&self, sink: SearchSink, query: TextQuery, filter: SearchFilter) {
+        let Ok(index) = self.text_index(&sink) else {
+            // Cancelled by a newer search
+            return;
+        };
+        for (entry, score) in index.search(&query) {
+            if filter.types.allows_kind(entry.kind)
+                && let Some(data) = self.get_entry(entry)
+                && filter.matches(&data)
+                && sink.send(data, score).is_err()
+            {
+                // Cancelled by a newer search
+                break;
+            }
+        }
+    }
+
+    /// Returns the entries of every searchable table.
+    pub fn entries(&self) -> Vec<&InvestmentData> {
+        self.entries_of(&TypeFilter::default())
+    }
+
+    /// Returns the entries of the searchable tables `types` doesn't hide, without going through the hidden ones.
+    pub fn entries_of(&self, types: &TypeFilter) -> Vec<&InvestmentData> {
+        let destinations = types.allows_kind(InvestmentKind::Destination)
+            || types.allows_kind(InvestmentKind::Place);
+        [
+            (InvestmentKind::InventoryItem, self.items.values()),
+            (InvestmentKind::Activity, self.activities.values()),
+            (InvestmentKind::Progression, self.progressions.values()),
+            (InvestmentKind::Season, self.seasons.values()),
+        ]
+        .into_iter()
+        .filter(|(kind, _)| types.allows_kind(*kind))
+        .flat_map(|(_, values)| values)
+        .chain(
+            destinations
+                .then(|| self.destinations.values())
+                .into_iter()
+                .flatten(),
+        )
+        .collect()
+    }
+
+    /// Runs a structured query over every table.
+    #[tracing::instrument(skip(self))]
+    pub fn search_by_query(&self, sink: SearchSink, query: Query, filter: SearchFilter) {
+        if self
+            .entries_of(&filter.types)
+            .par_iter()
+            .try_for_each(|data| -> anyhow::Result<()> {
+                sink.check()?;
+                if query.matches(data) && filter.matches(data) {
+                    sink.send((*data).clone(), 0)?;
+                }
+                Ok(())
+            })
+            .is_err()
+        {
+            // Cancelled by a newer search
+        };
+    }
+
+    /// Looks up the entry an `EntryRef` points to.
+    #[tracing::instrument(skip(self))]
+    pub fn get_entry(&self, entry: EntryRef) -> Option<InvestmentData> {
+        let data = match entry.kind {
+            InvestmentKind::Activity => self.activities.get_by_hash(entry.hash),
+            InvestmentKind::InventoryItem => self.items.get_by_hash(entry.hash),
+            InvestmentKind::Progression => self.progressions.get_by_hash(entry.hash),
+            InvestmentKind::Season => self.seasons.get_by_hash(entry.hash),
+            InvestmentKind::Destination | InvestmentKind::Place => {
+                self.destinations.get_by_hash(entry.hash)
+            }
+            _ => None,
+        }?;
+        (data.kind() == entry.kind).then_some(data)
+    }
+
+    /// Returns the index of an entry in its table.
+    pub fn index_of(&self, entry: EntryRef) -> Option<usize> {
+        match entry.kind {
+            InvestmentKind::Activity => self.activities.index_of_hash(entry.hash),
+            InvestmentKind::InventoryItem => self.items.index_of_hash(entry.hash),
+            InvestmentKind::Progression => self.progressions.index_of_hash(entry.hash),
+            InvestmentKind::Season => self.seasons.index_of_hash(entry.hash),
+            InvestmentKind::Destination => self.destinations.destination_index_of_hash(entry.hash),
+            InvestmentKind::Place => self.destinations.place_index_of_hash(entry.hash),
+            _ => None,
+        }
+    }
+
+    /// Returns what other entries point to when they reference `entry`, if anything can.
+    pub fn reference_target(&self, entry: &InvestmentData) -> Option<ReferenceTarget> {
+        let hash = entry.hash();
+        match entry {
+            InvestmentData::InventoryItem(_) => {
+                self.items.index_of_hash(hash).map(ReferenceTarget::Item)
+            }
+            InvestmentData::Progression(_) => self
+                .progressions
+                .index_of_hash(hash)
+                .map(ReferenceTarget::Progression),
+            InvestmentData::Destination(_) => self
+                .destinations
+                .destination_index_of_hash(hash)
+                .map(ReferenceTarget::Destination),
+            InvestmentData::Place(_) => self
+                .destinations
+                .place_index_of_hash(hash)
+                .map(ReferenceTarget::Place),
+            _ => None,
+        }
+    }
+
+    /// Scans every table for entries whose hash starts with the decimal or hex digits of `query`.
+    #[tracing::instrument(skip(self))]
+    pub fn search_by_partial_hash(&self, sink: SearchSink, query: HashQuery, filter: SearchFilter) {
+        if self
+            .entries_of(&filter.types)
+            .par_iter()
+            .try_for_each(|data| -> anyhow::Result<()> {
+                sink.check()?;
+                if let Some(score) = query.rank(data.hash())
+                    && filter.matches(data)
+                {
+                    sink.send((*data).clone(), score)?;
+                }
+                Ok(())
+            })
+            .is_err()
+        {
+            // Cancelled by a newer search
+        };
+    }
+
+    /// Sends every entry that was read from, or draws, the package tag `tag`.
+    #[tracing::instrument(skip(self))]
+    pub fn search_by_tag(&self, sink: SearchSink, tag: TagHash, filter: SearchFilter) {
+        let mut sent = FxHashSet::default();
+        for owner in self.tags().owners(tag) {
+            if sent.insert(owner.entry)
+                && let Some(data) = self.get_entry(owner.entry)
+                && filter.matches(&data)
+                && sink.send(data, TAG_MATCH_SCORE).is_err()
+            {
+                // Cancelled by a newer search
+                break;
+            }
+        }
+    }
+
     /// Get anything from a hash. Scans all tables for any containing this hash and returns a vec of results.
     #[tracing::instrument(skip(self))]
     pub fn search_by_hash(&self, hash: u32) -> Vec<InvestmentData> {
@@ -89,27 +392,85 @@ impl InvestmentManager {
             results.push(item);
         }
 
+        if let Some(prog) = self.progressions.get_by_hash(hash) {
+            results.push(prog);
+        }
+
+        if let Some(season) = self.seasons.get_by_hash(hash) {
+            results.push(season);
+        }
+
+        if let Some(dest) = self.destinations.get_by_hash(hash) {
+            results.push(dest);
+        }
+
         results
     }
 
-    /// Get anything from a name. Scans all tables for any containing "name" and returns a vec of results.
+    /// Get anything from a name. Searches all tables at once, and returns when all of them are done.
     #[tracing::instrument(skip(self))]
-    pub fn search_by_name(
-        &self,
-        search_channel: Arc<crossbeam::channel::Sender<InvestmentData>>,
-        name: String,
-    ) {
-        let act = self.activities.clone();
-        let act_channel = search_channel.clone();
-        let name1 = name.to_lowercase().clone();
-        std::thread::spawn(move || act.search_by_name(act_channel, name1));
-        let item_channel = search_channel.clone();
-        let items = self.items.clone();
-        let name1 = name.to_lowercase().clone();
-        std::thread::spawn(move || items.search_by_name(item_channel, name1));
+    pub fn search_by_name(&self, sink: SearchSink, name: String, filter: SearchFilter) {
+        let name = name.to_lowercase();
+        let types = filter.types.clone();
+        // Scoped, so this only returns once every table has been searched
+        std::thread::scope(|s| {
+            if types.allows_kind(InvestmentKind::Activity) {
+                s.spawn(|| {
+                    self.activities
+                        .search_by_name(sink.clone(), name.clone(), filter.clone())
+                });
+            }
+            if types.allows_kind(InvestmentKind::InventoryItem) {
+                s.spawn(|| {
+                    self.items
+                        .search_by_name(sink.clone(), name.clone(), filter.clone())
+                });
+            }
+            if types.allows_kind(InvestmentKind::Progression) {
+                s.spawn(|| {
+                    self.progressions
+                        .search_by_name(sink.clone(), name.clone(), filter.clone())
+                });
+            }
+            if types.allows_kind(InvestmentKind::Season) {
+                s.spawn(|| {
+                    self.seasons
+                        .search_by_name(sink.clone(), name.clone(), filter.clone())
+                });
+            }
+            if types.allows_kind(InvestmentKind::Destination)
+                || types.allows_kind(InvestmentKind::Place)
+            {
+                s.spawn(|| {
+                    self.destinations
+                        .search_by_name(sink.clone(), name.clone(), filter.clone())
+                });
+            }
+        });
     }
 }
 
+/// Sends the entries whose name matches `name`, allowing for typos, with a score for how well it matched.
+/// Stops early when a newer search cancels `sink`.
+fn search_names(entries: &[InvestmentData], sink: &SearchSink, name: &str, filter: &SearchFilter) {
+    if entries
+        .par_iter()
+        .try_for_each(|entry| -> Result<(), SearchCancelled> {
+            sink.check()?;
+            let entry_name = entry.name_string().and_then(|n| n.get());
+            if let Some(score) = rank_name(name, entry_name.as_deref())
+                && filter.matches(entry)
+            {
+                sink.send(entry.clone(), score)?;
+            }
+            Ok(())
+        })
+        .is_err()
+    {
+        // Cancelled by a newer search
+    };
+}
+
 impl StringManager {
     #[tracing::instrument]
     pub fn new() -> anyhow::Result<Self> {
@@ -218,34 +579,10 @@ impl ActivityManager {
         }
         Ok(Self { activities })
     }
-    /// Returns a Vec containing the activities with `name` in their name.
+    /// Sends the activities whose name matches `name`, allowing for typos, with a score for how well it matched.
     #[tracing::instrument(skip(self))]
-    pub fn search_by_name(
-        &self,
-        channel: Arc<crossbeam::channel::Sender<InvestmentData>>,
-        name: String,
-    ) {
-        if self
-            .activities
-            .values()
-            .par_iter()
-            .try_for_each(move |act| -> anyhow::Result<()> {
-                if let InvestmentData::Activity(a) = act {
-                    let act_name = a.display.display_properties.name.get();
-                    if act_name
-                        .as_ref()
-                        .is_some_and(|n| n.to_lowercase().contains(&name))
-                        || act_name.is_none() && name.is_empty()
-                    {
-                        channel.try_send(InvestmentData::Activity(a.clone()))?;
-                    }
-                }
-                Ok(())
-            })
-            .is_err()
-        {
-            // Silently error, for now?
-        };
+    pub fn search_by_name(&self, sink: SearchSink, name: String, filter: SearchFilter) {
+        search_names(self.activities.values(), &sink, &name, &filter);
     }
 
     /// Returns an activity's data by it's hash.
@@ -254,11 +591,37 @@ impl ActivityManager {
         self.activities.lookup_hash(&hash).cloned()
     }
 
+    /// Returns the index of an activity in the table by its hash.
+    pub fn index_of_hash(&self, hash: u32) -> Option<usize> {
+        self.activities.index_of(&hash)
+    }
+
     /// Returns an activity's data by it's index in the table.
     #[tracing::instrument(skip(self))]
     pub fn get_activity_by_index(&self, index: u16) -> Option<InvestmentData> {
         self.activities.get(index as usize).cloned()
     }
+
+    /// Returns every activity, in table order.
+    pub fn values(&self) -> &Vec<InvestmentData> {
+        self.activities.values()
+    }
+
+    /// Returns every activity that takes place at the destination at `destination_index`.
+    #[tracing::instrument(skip(self))]
+    pub fn get_by_destination(&self, destination_index: usize) -> Vec<InvestmentData> {
+        self.activities
+            .values()
+            .iter()
+            .filter(|act| match act {
+                InvestmentData::Activity(a) => {
+                    usize::try_from(a.data.destination_index).is_ok_and(|i| i == destination_index)
+                }
+                _ => false,
+            })
+            .cloned()
+            .collect()
+    }
 }
 
 impl ItemManager {
@@ -278,41 +641,17 @@ impl ItemManager {
             item_map.insert(
                 data.hash,
                 InvestmentData::InventoryItem(Box::new(InventoryItem::new(
-                    data.item.0.clone(),
-                    item_display.string_tag.0.clone(),
+                    &data.item,
+                    &item_display.string_tag,
                 ))),
             );
         }
         Ok(Self { items: item_map })
     }
-    /// Returns a Vec containing the items with `name` in their name.
+    /// Sends the items whose name matches `name`, allowing for typos, with a score for how well it matched.
     #[tracing::instrument(skip(self))]
-    pub fn search_by_name(
-        &self,
-        channel: Arc<crossbeam::channel::Sender<InvestmentData>>,
-        name: String,
-    ) {
-        if self
-            .items
-            .values()
-            .par_iter()
-            .try_for_each(move |item| -> anyhow::Result<()> {
-                if let InvestmentData::InventoryItem(i) = item {
-                    let item_name = i.display.name.get();
-                    if item_name
-                        .as_ref()
-                        .is_some_and(|n| n.to_lowercase().contains(&name))
-                        || item_name.is_none() && name.is_empty()
-                    {
-                        channel.try_send(InvestmentData::InventoryItem(i.clone()))?;
-                    }
-                }
-                Ok(())
-            })
-            .is_err()
-        {
-            // Silently error, for now?
-        };
+    pub fn search_by_name(&self, sink: SearchSink, name: String, filter: SearchFilter) {
+        search_names(self.items.values(), &sink, &name, &filter);
     }
 
     /// Returns an item's data by it's hash.
@@ -326,6 +665,25 @@ impl ItemManager {
     pub fn get_activity_by_index(&self, index: u16) -> Option<InvestmentData> {
         self.items.get(index as usize).cloned()
     }
+
+    /// Returns an item by its index in the table, without the `InvestmentData` wrapper.
+    #[tracing::instrument(skip(self))]
+    pub fn get_item_by_index(&self, index: usize) -> Option<InventoryItem> {
+        match self.items.get(index)? {
+            InvestmentData::InventoryItem(i) => Some(*i.clone()),
+            _ => None,
+        }
+    }
+
+    /// Returns the index of an item in the table by its hash.
+    pub fn index_of_hash(&self, hash: u32) -> Option<usize> {
+        self.items.index_of(&hash)
+    }
+
+    /// Returns every item, in table order.
+    pub fn values(&self) -> &Vec<InvestmentData> {
+        self.items.values()
+    }
 }
 
 impl IconManager {
@@ -0,0 +1,43 @@
+use tiger_parse::{PackageManagerExt, TigerReadable};
+use tiger_pkg::package_manager;
+
+use crate::{
+    IndexableHashMap,
+    data::objective::{SObjective, SObjectiveList},
+};
+
+pub struct ObjectiveManager {
+    objectives: IndexableHashMap<u32, SObjective>,
+}
+
+impl ObjectiveManager {
+    #[tracing::instrument]
+    pub fn new() -> anyhow::Result<Self> {
+        let objective_tag: SObjectiveList = package_manager().read_tag_struct(
+            package_manager().get_all_by_reference(SObjectiveList::ID.unwrap())[0].0,
+        )?;
+
+        let mut objectives = IndexableHashMap::new();
+        for data in objective_tag.objectives.iter() {
+            objectives.insert(data.hash, data.objective.0.clone());
+        }
+        Ok(Self { objectives })
+    }
+
+    /// Returns an objective by its hash.
+    #[tracing::instrument(skip(self))]
+    pub fn get_by_hash(&self, hash: u32) -> Option<SObjective> {
+        self.objectives.lookup_hash(&hash).cloned()
+    }
+
+    /// Returns an objective by its index in the table.
+    #[tracing::instrument(skip(self))]
+    pub fn get_by_index(&self, index: usize) -> Option<SObjective> {
+        self.objectives.get(index).cloned()
+    }
+
+    /// Returns every objective, in table order.
+    pub fn values(&self) -> &Vec<SObjective> {
+        self.objectives.values()
+    }
+}
@@ -0,0 +1,186 @@
+use std::sync::OnceLock;
+
+use rustc_hash::FxHashMap;
+use tiger_parse::{PackageManagerExt, TigerReadable};
+use tiger_pkg::package_manager;
+
+use crate::{
+    IndexableHashMap, InvestmentData,
+    data::{
+        item::S808077C3,
+        plug::{CosmeticKind, SPlugSet, SPlugSetList},
+    },
+    global_instance::investment_manager,
+};
+
+pub struct PlugManager {
+    plug_sets: IndexableHashMap<u32, SPlugSet>,
+    usage: OnceLock<PlugUsage>,
+}
+
+/// Which items can use which plugs, either directly or through a plug set.
+#[derive(Default)]
+struct PlugUsage {
+    item_plug_sets: FxHashMap<usize, Vec<usize>>,
+    item_plugs: FxHashMap<usize, Vec<usize>>,
+    plug_set_items: FxHashMap<usize, Vec<usize>>,
+    plug_items: FxHashMap<usize, Vec<usize>>,
+    plug_plug_sets: FxHashMap<usize, Vec<usize>>,
+}
+
+impl PlugManager {
+    #[tracing::instrument]
+    pub fn new() -> anyhow::Result<Self> {
+        let plug_set_tag: SPlugSetList = package_manager().read_tag_struct(
+            package_manager().get_all_by_reference(SPlugSetList::ID.unwrap())[0].0,
+        )?;
+
+        let mut plug_sets = IndexableHashMap::new();
+        for data in plug_set_tag.plug_sets.iter() {
+            plug_sets.insert(data.hash, data.plug_set.0.clone());
+        }
+        Ok(Self {
+            plug_sets,
+            usage: OnceLock::new(),
+        })
+    }
+
+    /// Returns a plug set by its index in the table.
+    #[tracing::instrument(skip(self))]
+    pub fn get_plug_set_by_index(&self, index: usize) -> Option<SPlugSet> {
+        self.plug_sets.get(index).cloned()
+    }
+
+    /// Returns the item indices of every plug in the plug set at `index`.
+    pub fn plug_set_items(&self, index: usize) -> Vec<usize> {
+        self.plug_sets
+            .get(index)
+            .map(|set| {
+                set.entries
+                    .iter()
+                    .filter_map(|e| usize::try_from(e.item_index).ok())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns the item indices of the cosmetics of `kind` that can be applied to the item at `item_index`.
+    #[tracing::instrument(skip(self))]
+    pub fn cosmetics_for_item(&self, item_index: usize, kind: CosmeticKind) -> Vec<usize> {
+        let usage = self.usage();
+        let mut plugs: Vec<usize> = usage
+            .item_plug_sets
+            .get(&item_index)
+            .into_iter()
+            .flatten()
+            .flat_map(|set| self.plug_set_items(*set))
+            .chain(
+                usage
+                    .item_plugs
+                    .get(&item_index)
+                    .into_iter()
+                    .flatten()
+                    .copied(),
+            )
+            .collect();
+        plugs.sort_unstable();
+        plugs.dedup();
+
+        let items = investment_manager().items();
+        plugs.retain(|plug| {
+            items
+                .get_item_by_index(*plug)
+                .is_some_and(|p| p.cosmetic_kind() == Some(kind))
+        });
+        plugs
+    }
+
+    /// Returns the item indices of every item the plug at `plug_index` can be applied to.
+    #[tracing::instrument(skip(self))]
+    pub fn items_for_plug(&self, plug_index: usize) -> Vec<usize> {
+        let usage = self.usage();
+        let mut items: Vec<usize> = usage
+            .plug_plug_sets
+            .get(&plug_index)
+            .into_iter()
+            .flatten()
+            .flat_map(|set| usage.plug_set_items.get(set).into_iter().flatten())
+            .chain(usage.plug_items.get(&plug_index).into_iter().flatten())
+            .copied()
+            .collect();
+        items.sort_unstable();
+        items.dedup();
+        items
+    }
+
+    fn usage(&self) -> &PlugUsage {
+        self.usage.get_or_init(|| self.build_usage())
+    }
+
+    #[tracing::instrument(skip(self))]
+    fn build_usage(&self) -> PlugUsage {
+        let mut usage = PlugUsage::default();
+
+        for (set_index, set) in self.plug_sets.values().iter().enumerate() {
+            for entry in &set.entries {
+                if let Ok(plug) = usize::try_from(entry.item_index) {
+                    usage
+                        .plug_plug_sets
+                        .entry(plug)
+                        .or_default()
+                        .push(set_index);
+                }
+            }
+        }
+
+        let items = investment_manager().items();
+        for (item_index, data) in items.values().iter().enumerate() {
+            let InvestmentData::InventoryItem(item) = data else {
+                continue;
+            };
+            let Some(sockets) = &item.data.sockets.0 else {
+                continue;
+            };
+
+            for socket in &sockets.data {
+                for set in socket_plug_sets(socket) {
+                    usage
+                        .item_plug_sets
+                        .entry(item_index)
+                        .or_default()
+                        .push(set);
+                    usage
+                        .plug_set_items
+                        .entry(set)
+                        .or_default()
+                        .push(item_index);
+                }
+                for plug in socket_plugs(socket) {
+                    usage.item_plugs.entry(item_index).or_default().push(plug);
+                    usage.plug_items.entry(plug).or_default().push(item_index);
+                }
+            }
+        }
+
+        usage
+    }
+}
+
+/// Returns the plug set indices a socket takes its plugs from.
+pub fn socket_plug_sets(socket: &S808077C3) -> Vec<usize> {
+    [
+        socket.reusable_plugset_index,
+        socket.randomized_plugset_index,
+    ]
+    .into_iter()
+    .filter_map(|i| usize::try_from(i).ok())
+    .collect()
+}
+
+/// Returns the item indices of the plugs a socket defines directly, including its initial plug.
+pub fn socket_plugs(socket: &S808077C3) -> Vec<usize> {
+    std::iter::once(socket.single_initial_item_index)
+        .chain(socket.plug_items.iter().map(|p| p.plug_index))
+        .filter_map(|i| usize::try_from(i).ok())
+        .collect()
+}
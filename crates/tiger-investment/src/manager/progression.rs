@@ -0,0 +1,73 @@
+use tiger_parse::{PackageManagerExt, TigerReadable};
+use tiger_pkg::package_manager;
+
+use crate::{
+    IndexableHashMap, InvestmentData,
+    data::progression::{Progression, SProgressionDisplayList, SProgressionList},
+    manager::search_names,
+    search::{SearchFilter, pipeline::SearchSink},
+};
+
+pub struct ProgressionManager {
+    progressions: IndexableHashMap<u32, InvestmentData>,
+}
+
+impl ProgressionManager {
+    #[tracing::instrument]
+    pub fn new() -> anyhow::Result<Self> {
+        let progression_display_list: SProgressionDisplayList = package_manager().read_tag_struct(
+            package_manager().get_all_by_reference(SProgressionDisplayList::ID.unwrap())[0].0,
+        )?;
+        let progression_data: SProgressionList = package_manager().read_tag_struct(
+            package_manager().get_all_by_reference(SProgressionList::ID.unwrap())[0].0,
+        )?;
+
+        if progression_display_list.progressions.len() != progression_data.progressions.len() {
+            return Err(anyhow::anyhow!("Progression table size does not match"));
+        }
+
+        let mut progressions = IndexableHashMap::new();
+        for (i, p_data) in progression_data.progressions.iter().enumerate() {
+            let p_disp = &progression_display_list.progressions[i];
+            progressions.insert(
+                p_data.hash,
+                InvestmentData::Progression(Box::new(Progression::new(
+                    p_data.progression.0.clone(),
+                    p_disp.progression.0.clone(),
+                ))),
+            );
+        }
+        Ok(Self { progressions })
+    }
+
+    /// Sends the progressions whose name matches `name`, allowing for typos, with a score for how well it matched.
+    #[tracing::instrument(skip(self))]
+    pub fn search_by_name(&self, sink: SearchSink, name: String, filter: SearchFilter) {
+        search_names(self.progressions.values(), &sink, &name, &filter);
+    }
+
+    /// Returns a progression's data by its hash.
+    #[tracing::instrument(skip(self))]
+    pub fn get_by_hash(&self, hash: u32) -> Option<InvestmentData> {
+        self.progressions.lookup_hash(&hash).cloned()
+    }
+
+    /// Returns a progression by its index in the table, without the `InvestmentData` wrapper.
+    #[tracing::instrument(skip(self))]
+    pub fn get_progression_by_index(&self, index: usize) -> Option<Progression> {
+        match self.progressions.get(index)? {
+            InvestmentData::Progression(p) => Some(*p.clone()),
+            _ => None,
+        }
+    }
+
+    /// Returns the index of a progression in the table by its hash.
+    pub fn index_of_hash(&self, hash: u32) -> Option<usize> {
+        self.progressions.index_of(&hash)
+    }
+
+    /// Returns every progression, in table order.
+    pub fn values(&self) -> &Vec<InvestmentData> {
+        self.progressions.values()
+    }
+}
@@ -0,0 +1,408 @@
+use rustc_hash::FxHashMap;
+
+use crate::{
+    EntryRef, InvestmentData, data::text::IndexedString, global_instance::investment_manager,
+};
+
+/// Something an investment entry can point to with an index or string.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, strum::Display)]
+pub enum ReferenceTarget {
+    Item(usize),
+    Icon(usize),
+    SandboxPerk(usize),
+    PlugSet(usize),
+    Objective(usize),
+    Progression(usize),
+    SeasonPass(usize),
+    Destination(usize),
+    Place(usize),
+    Category(usize),
+    StatGroup(usize),
+    EquipableItemSet(usize),
+    UnlockFlag(usize),
+    /// A localized string, by its string container index and hash
+    String(u32, u32),
+}
+
+impl ReferenceTarget {
+    pub fn string(string: &IndexedString) -> Option<Self> {
+        string.valid().then_some(Self::String(string.0, string.1))
+    }
+
+    /// Returns true for targets that are entries themselves, rather than something entries share.
+    pub fn is_entry(&self) -> bool {
+        matches!(
+            self,
+            Self::Item(_) | Self::Progression(_) | Self::Destination(_) | Self::Place(_)
+        )
+    }
+}
+
+/// An entry pointing to a target through one of its fields.
+#[derive(Clone, Copy, Debug)]
+pub struct Reference {
+    pub from: EntryRef,
+    pub field: &'static str,
+}
+
+/// Reverse lookup from anything an entry can point to, to every entry pointing to it.
+#[derive(Default)]
+pub struct ReferenceIndex {
+    references: FxHashMap<ReferenceTarget, Vec<Reference>>,
+    /// The targets each entry points to that aren't entries, with the first field pointing to each
+    shared: FxHashMap<EntryRef, Vec<(ReferenceTarget, &'static str)>>,
+}
+
+impl ReferenceIndex {
+    /// Returns every entry referencing `target`.
+    pub fn referenced_by(&self, target: ReferenceTarget) -> &[Reference] {
+        self.references
+            .get(&target)
+            .map(|r| r.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Returns the plug sets, icons, strings and other targets `entry` points to, which other
+    /// entries can share.
+    pub fn shared_targets(&self, entry: EntryRef) -> &[(ReferenceTarget, &'static str)] {
+        self.shared
+            .get(&entry)
+            .map(|t| t.as_slice())
+            .unwrap_or_default()
+    }
+
+    fn add(&mut self, target: ReferenceTarget, from: EntryRef, field: &'static str) {
+        self.references
+            .entry(target)
+            .or_default()
+            .push(Reference { from, field });
+
+        if !target.is_entry() {
+            let shared = self.shared.entry(from).or_default();
+            if !shared.iter().any(|(t, _)| *t == target) {
+                shared.push((target, field));
+            }
+        }
+    }
+
+    fn add_index(
+        &mut self,
+        target: fn(usize) -> ReferenceTarget,
+        index: i16,
+        from: EntryRef,
+        field: &'static str,
+    ) {
+        if let Ok(index) = usize::try_from(index) {
+            self.add(target(index), from, field);
+        }
+    }
+
+    fn add_string(&mut self, string: &IndexedString, from: EntryRef, field: &'static str) {
+        if let Some(target) = ReferenceTarget::string(string) {
+            self.add(target, from, field);
+        }
+    }
+
+    /// Goes over every typed index field of every table.
+    #[tracing::instrument]
+    pub fn build() -> Self {
+        let mut index = Self::default();
+        let im = investment_manager();
+
+        for entry in im.items().values() {
+            index.add_entry(entry);
+        }
+        for entry in im.activities().values() {
+            index.add_entry(entry);
+        }
+        for entry in im.progressions().values() {
+            index.add_entry(entry);
+        }
+        for entry in im.seasons().values() {
+            index.add_entry(entry);
+        }
+        for entry in im.destinations().values() {
+            index.add_entry(entry);
+        }
+
+        index
+    }
+
+    fn add_entry(&mut self, entry: &InvestmentData) {
+        let from = entry.entry_ref();
+        if let Some(icon_index) = entry.icon_index() {
+            self.add_index(ReferenceTarget::Icon, icon_index, from, "icon_index");
+        }
+        if let Some(name) = entry.name_string() {
+            self.add_string(&name, from, "name");
+        }
+
+        match entry {
+            InvestmentData::InventoryItem(item) => {
+                let display = &item.display;
+                self.add_string(&display.item_type, from, "item_type");
+                self.add_string(&display.display_source, from, "display_source");
+                self.add_string(&display.toast, from, "toast");
+                self.add_string(&display.flavor, from, "flavor");
+
+                let data = &item.data;
+                if let Some(sockets) = &data.sockets.0 {
+                    for socket in &sockets.data {
+                        self.add_index(
+                            ReferenceTarget::Item,
+                            socket.single_initial_item_index,
+                            from,
+                            "sockets.single_initial_item_index",
+                        );
+                        self.add_index(
+                            ReferenceTarget::PlugSet,
+                            socket.reusable_plugset_index,
+                            from,
+                            "sockets.reusable_plugset_index",
+                        );
+                        self.add_index(
+                            ReferenceTarget::PlugSet,
+                            socket.randomized_plugset_index,
+                            from,
+                            "sockets.randomized_plugset_index",
+                        );
+                        for plug in &socket.plug_items {
+                            self.add_index(
+                                ReferenceTarget::Item,
+                                plug.plug_index,
+                                from,
+                                "sockets.plug_items",
+                            );
+                        }
+                    }
+                }
+                if let Some(stats_perks) = &data.stats_perks.0 {
+                    for perk in &stats_perks.perks {
+                        self.add_index(
+                            ReferenceTarget::SandboxPerk,
+                            perk.sandbox_perk_index,
+                            from,
+                            "perks",
+                        );
+                    }
+                    self.add_index(
+                        ReferenceTarget::StatGroup,
+                        stats_perks.stat_group_index,
+                        from,
+                        "stat_group_index",
+                    );
+                }
+                if let Some(objectives) = &data.objectives.0 {
+                    for objective in &objectives.objective_indices {
+                        self.add_index(ReferenceTarget::Objective, *objective, from, "objectives");
+                    }
+                }
+                if let Some(set_data) = &data.set_data.0 {
+                    for set_item in &set_data.items {
+                        self.add_index(
+                            ReferenceTarget::Item,
+                            set_item.item_index,
+                            from,
+                            "set_data.items",
+                        );
+                    }
+                    self.add_index(
+                        ReferenceTarget::Item,
+                        set_data.quest_line_item_index,
+                        from,
+                        "set_data.quest_line_item_index",
+                    );
+                }
+                for category in &data.category_indices {
+                    self.add_index(
+                        ReferenceTarget::Category,
+                        *category,
+                        from,
+                        "category_indices",
+                    );
+                }
+                self.add_index(
+                    ReferenceTarget::EquipableItemSet,
+                    data.equipable_item_set_index,
+                    from,
+                    "equipable_item_set_index",
+                );
+            }
+            InvestmentData::Activity(act) => {
+                self.add_index(
+                    ReferenceTarget::Destination,
+                    act.data.destination_index,
+                    from,
+                    "destination_index",
+                );
+                self.add_index(
+                    ReferenceTarget::Place,
+                    act.data.place_index,
+                    from,
+                    "place_index",
+                );
+                for phase in &act.data.insertion_points.0.phases {
+                    self.add(
+                        ReferenceTarget::UnlockFlag(phase.unlock_index as usize),
+                        from,
+                        "insertion_points.unlock_index",
+                    );
+                }
+                for requirement in act.requirements() {
+                    self.add(
+                        ReferenceTarget::UnlockFlag(requirement.unlock_index as usize),
+                        from,
+                        "requirements.unlock_index",
+                    );
+                }
+
+                let display = &act.display;
+                self.add_string(&display.display_properties.description, from, "description");
+                for reward in &display.rewards.rewards {
+                    for item in &reward.items {
+                        self.add_index(ReferenceTarget::Item, item.item_index, from, "rewards");
+                    }
+                }
+                for modifier in &display.modifiers {
+                    self.add_index(
+                        ReferenceTarget::Icon,
+                        modifier.data.icon_index,
+                        from,
+                        "modifiers.icon_index",
+                    );
+                    self.add_string(&modifier.data.name, from, "modifiers.name");
+                }
+            }
+            InvestmentData::Progression(progression) => {
+                self.add_string(&progression.display.description, from, "description");
+                for step in &progression.display.steps {
+                    self.add_index(
+                        ReferenceTarget::Icon,
+                        step.icon_index,
+                        from,
+                        "steps.icon_index",
+                    );
+                    self.add_string(&step.step_name, from, "steps.step_name");
+                }
+                for reward in &progression.data.reward_items {
+                    self.add_index(
+                        ReferenceTarget::Item,
+                        reward.item_index,
+                        from,
+                        "reward_items",
+                    );
+                }
+            }
+            InvestmentData::Season(season) => {
+                self.add_string(&season.data.description, from, "description");
+                self.add_index(
+                    ReferenceTarget::Item,
+                    season.data.artifact_item_index,
+                    from,
+                    "artifact_item_index",
+                );
+                for pass_index in &season.data.season_pass_indices {
+                    self.add_index(
+                        ReferenceTarget::SeasonPass,
+                        *pass_index,
+                        from,
+                        "season_pass_indices",
+                    );
+                }
+                for pass in season.season_passes() {
+                    self.add_index(
+                        ReferenceTarget::Progression,
+                        pass.data.reward_progression_index,
+                        from,
+                        "season_pass.reward_progression_index",
+                    );
+                    self.add_index(
+                        ReferenceTarget::Progression,
+                        pass.data.prestige_progression_index,
+                        from,
+                        "season_pass.prestige_progression_index",
+                    );
+                    for reward in &pass.data.rewards {
+                        self.add_index(
+                            ReferenceTarget::Item,
+                            reward.item_index,
+                            from,
+                            "season_pass.rewards",
+                        );
+                    }
+                }
+            }
+            InvestmentData::Destination(destination) => {
+                self.add_string(&destination.data.description, from, "description");
+                self.add_index(
+                    ReferenceTarget::Place,
+                    destination.data.place_index,
+                    from,
+                    "place_index",
+                );
+            }
+            InvestmentData::Place(place) => {
+                self.add_string(&place.data.description, from, "description");
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InvestmentKind;
+
+    fn item(hash: u32) -> EntryRef {
+        EntryRef {
+            kind: InvestmentKind::InventoryItem,
+            hash,
+        }
+    }
+
+    #[test]
+    fn shared_targets_skip_entries_and_repeats() {
+        let mut index = ReferenceIndex::default();
+        index.add(
+            ReferenceTarget::PlugSet(3),
+            item(1),
+            "sockets.reusable_plugset_index",
+        );
+        index.add(
+            ReferenceTarget::PlugSet(3),
+            item(1),
+            "sockets.randomized_plugset_index",
+        );
+        index.add(
+            ReferenceTarget::Item(7),
+            item(1),
+            "sockets.single_initial_item_index",
+        );
+        index.add(ReferenceTarget::UnlockFlag(5), item(1), "unlock_flags");
+        index.add(
+            ReferenceTarget::PlugSet(3),
+            item(2),
+            "sockets.reusable_plugset_index",
+        );
+
+        assert_eq!(
+            index.shared_targets(item(1)),
+            [
+                (
+                    ReferenceTarget::PlugSet(3),
+                    "sockets.reusable_plugset_index"
+                ),
+                (ReferenceTarget::UnlockFlag(5), "unlock_flags"),
+            ]
+        );
+        assert!(index.shared_targets(item(3)).is_empty());
+
+        let sharing: Vec<EntryRef> = index
+            .referenced_by(ReferenceTarget::PlugSet(3))
+            .iter()
+            .map(|r| r.from)
+            .collect();
+        assert_eq!(sharing, [item(1), item(1), item(2)]);
+    }
+}
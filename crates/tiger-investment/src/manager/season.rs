@@ -0,0 +1,106 @@
+use std::sync::OnceLock;
+
+use rustc_hash::FxHashMap;
+use tiger_parse::{PackageManagerExt, TigerReadable};
+use tiger_pkg::package_manager;
+
+use crate::{
+    IndexableHashMap, InvestmentData,
+    data::season::{SSeasonList, SSeasonPassList, Season, SeasonPass, SeasonPassReward},
+    manager::search_names,
+    search::{SearchFilter, pipeline::SearchSink},
+};
+
+pub struct SeasonManager {
+    seasons: IndexableHashMap<u32, InvestmentData>,
+    season_passes: IndexableHashMap<u32, SeasonPass>,
+    /// Season pass rewards by the index of the item they grant.
+    item_rewards: OnceLock<FxHashMap<usize, Vec<(Season, SeasonPassReward)>>>,
+}
+
+impl SeasonManager {
+    #[tracing::instrument]
+    pub fn new() -> anyhow::Result<Self> {
+        let season_tag: SSeasonList = package_manager().read_tag_struct(
+            package_manager().get_all_by_reference(SSeasonList::ID.unwrap())[0].0,
+        )?;
+        let season_pass_tag: SSeasonPassList = package_manager().read_tag_struct(
+            package_manager().get_all_by_reference(SSeasonPassList::ID.unwrap())[0].0,
+        )?;
+
+        let mut seasons = IndexableHashMap::new();
+        for data in season_tag.seasons.iter() {
+            seasons.insert(
+                data.hash,
+                InvestmentData::Season(Box::new(Season::new(data.season.0.clone()))),
+            );
+        }
+
+        let mut season_passes = IndexableHashMap::new();
+        for data in season_pass_tag.season_passes.iter() {
+            season_passes.insert(data.hash, SeasonPass::new(data.season_pass.0.clone()));
+        }
+
+        Ok(Self {
+            seasons,
+            season_passes,
+            item_rewards: OnceLock::new(),
+        })
+    }
+
+    /// Sends the seasons whose name matches `name`, allowing for typos, with a score for how well it matched.
+    #[tracing::instrument(skip(self))]
+    pub fn search_by_name(&self, sink: SearchSink, name: String, filter: SearchFilter) {
+        search_names(self.seasons.values(), &sink, &name, &filter);
+    }
+
+    /// Returns a season's data by its hash.
+    #[tracing::instrument(skip(self))]
+    pub fn get_by_hash(&self, hash: u32) -> Option<InvestmentData> {
+        self.seasons.lookup_hash(&hash).cloned()
+    }
+
+    /// Returns the index of a season in the table by its hash.
+    pub fn index_of_hash(&self, hash: u32) -> Option<usize> {
+        self.seasons.index_of(&hash)
+    }
+
+    /// Returns every season, in table order.
+    pub fn values(&self) -> &Vec<InvestmentData> {
+        self.seasons.values()
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn get_season_pass_by_index(&self, index: usize) -> Option<SeasonPass> {
+        self.season_passes.get(index).cloned()
+    }
+
+    /// Returns every season pass reward that grants the item at `item_index`, along with the season it belongs to.
+    pub fn rewards_for_item(&self, item_index: usize) -> &[(Season, SeasonPassReward)] {
+        self.item_rewards
+            .get_or_init(|| self.build_item_rewards())
+            .get(&item_index)
+            .map(|r| r.as_slice())
+            .unwrap_or_default()
+    }
+
+    #[tracing::instrument(skip(self))]
+    fn build_item_rewards(&self) -> FxHashMap<usize, Vec<(Season, SeasonPassReward)>> {
+        let mut item_rewards: FxHashMap<usize, Vec<(Season, SeasonPassReward)>> =
+            FxHashMap::default();
+        for season in self.seasons.values() {
+            let InvestmentData::Season(s) = season else {
+                continue;
+            };
+            for pass in s.season_passes() {
+                for reward in pass.rewards() {
+                    item_rewards
+                        .entry(reward.item_index)
+                        .or_default()
+                        .push((*s.clone(), reward));
+                }
+            }
+        }
+        item_rewards
+    }
+}
@@ -0,0 +1,38 @@
+use tiger_parse::{PackageManagerExt, TigerReadable};
+use tiger_pkg::package_manager;
+
+use crate::{
+    IndexableHashMap,
+    data::stat::{SStatGroup, SStatGroupList},
+};
+
+pub struct StatGroupManager {
+    stat_groups: IndexableHashMap<u32, SStatGroup>,
+}
+
+impl StatGroupManager {
+    #[tracing::instrument]
+    pub fn new() -> anyhow::Result<Self> {
+        let stat_group_tag: SStatGroupList = package_manager().read_tag_struct(
+            package_manager().get_all_by_reference(SStatGroupList::ID.unwrap())[0].0,
+        )?;
+
+        let mut stat_groups = IndexableHashMap::new();
+        for data in stat_group_tag.stat_groups.iter() {
+            stat_groups.insert(data.hash, data.stat_group.0.clone());
+        }
+        Ok(Self { stat_groups })
+    }
+
+    /// Returns a stat group by its hash.
+    #[tracing::instrument(skip(self))]
+    pub fn get_by_hash(&self, hash: u32) -> Option<SStatGroup> {
+        self.stat_groups.lookup_hash(&hash).cloned()
+    }
+
+    /// Returns a stat group by its index in the table.
+    #[tracing::instrument(skip(self))]
+    pub fn get_by_index(&self, index: usize) -> Option<SStatGroup> {
+        self.stat_groups.get(index).cloned()
+    }
+}
@@ -0,0 +1,69 @@
+use rustc_hash::FxHashMap;
+use tiger_pkg::TagHash;
+
+use crate::{EntryRef, InvestmentData, global_instance::investment_manager};
+
+/// An entry that was read from, or draws, a package tag.
+#[derive(Clone, Copy, Debug)]
+pub struct TagOwner {
+    pub entry: EntryRef,
+    /// What the tag is to the entry, e.g. "display" or "icon.primary.textures"
+    pub field: &'static str,
+}
+
+/// Lookup from package tags to the investment entries behind them.
+#[derive(Default)]
+pub struct TagIndex {
+    owners: FxHashMap<TagHash, Vec<TagOwner>>,
+}
+
+impl TagIndex {
+    /// Returns every entry owning `tag`.
+    pub fn owners(&self, tag: TagHash) -> &[TagOwner] {
+        self.owners
+            .get(&tag)
+            .map(|o| o.as_slice())
+            .unwrap_or_default()
+    }
+
+    fn add(&mut self, tag: TagHash, entry: EntryRef, field: &'static str) {
+        if tag.is_some() {
+            self.owners
+                .entry(tag)
+                .or_default()
+                .push(TagOwner { entry, field });
+        }
+    }
+
+    /// Collects the tags of every item, and the icons and textures drawn by every entry.
+    #[tracing::instrument]
+    pub fn build() -> Self {
+        let mut index = Self::default();
+        let im = investment_manager();
+        let icons = im.icons();
+        let mut icon_tags: FxHashMap<usize, Vec<(&'static str, TagHash)>> = FxHashMap::default();
+
+        for entry in im.entries() {
+            let from = entry.entry_ref();
+            if let InvestmentData::InventoryItem(item) = entry {
+                index.add(item.data_tag, from, "data");
+                index.add(item.display_tag, from, "display");
+            }
+
+            let Some(icon_index) = entry.icon_index().and_then(|i| usize::try_from(i).ok()) else {
+                continue;
+            };
+            let tags = icon_tags.entry(icon_index).or_insert_with(|| {
+                icons
+                    .get_index(icon_index)
+                    .map(|icon| icon.tag_hashes())
+                    .unwrap_or_default()
+            });
+            for &(field, tag) in tags.iter() {
+                index.add(tag, from, field);
+            }
+        }
+
+        index
+    }
+}
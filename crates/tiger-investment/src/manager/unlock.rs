@@ -0,0 +1,154 @@
+use std::sync::OnceLock;
+
+use rustc_hash::FxHashMap;
+use tiger_parse::{PackageManagerExt, TigerReadable};
+use tiger_pkg::package_manager;
+
+use crate::{
+    IndexableHashMap, InvestmentData,
+    data::unlock::{
+        SUnlockFlag, SUnlockFlagList, SUnlockValue, SUnlockValueList, Unlock, UnlockKind,
+        UnlockUsage,
+    },
+    global_instance::investment_manager,
+};
+
+pub struct UnlockManager {
+    flags: IndexableHashMap<u32, SUnlockFlag>,
+    values: IndexableHashMap<u32, SUnlockValue>,
+    /// Needs every activity, objective and item, so it's built on first use.
+    usages: OnceLock<FxHashMap<(UnlockKind, usize), Vec<UnlockUsage>>>,
+}
+
+impl UnlockManager {
+    #[tracing::instrument]
+    pub fn new() -> anyhow::Result<Self> {
+        let flag_tag: SUnlockFlagList = package_manager().read_tag_struct(
+            package_manager().get_all_by_reference(SUnlockFlagList::ID.unwrap())[0].0,
+        )?;
+        let value_tag: SUnlockValueList = package_manager().read_tag_struct(
+            package_manager().get_all_by_reference(SUnlockValueList::ID.unwrap())[0].0,
+        )?;
+
+        let mut flags = IndexableHashMap::new();
+        for data in flag_tag.flags.iter() {
+            flags.insert(data.hash, data.flag.0.clone());
+        }
+        let mut values = IndexableHashMap::new();
+        for data in value_tag.values.iter() {
+            values.insert(data.hash, data.value.0.clone());
+        }
+        Ok(Self {
+            flags,
+            values,
+            usages: OnceLock::new(),
+        })
+    }
+
+    /// Returns an unlock flag by its index in the table.
+    #[tracing::instrument(skip(self))]
+    pub fn get_flag_by_index(&self, index: usize) -> Option<SUnlockFlag> {
+        self.flags.get(index).cloned()
+    }
+
+    /// Returns an unlock value by its index in the table.
+    #[tracing::instrument(skip(self))]
+    pub fn get_value_by_index(&self, index: usize) -> Option<SUnlockValue> {
+        self.values.get(index).cloned()
+    }
+
+    /// Resolves an unlock index to its hash and name, `None` if it's out of range.
+    #[tracing::instrument(skip(self))]
+    pub fn resolve(&self, kind: UnlockKind, index: usize) -> Option<Unlock> {
+        let (hash, name) = match kind {
+            UnlockKind::Flag => {
+                let flag = self.flags.get(index)?;
+                (flag.hash, flag.name.get())
+            }
+            UnlockKind::Value => (self.values.get(index)?.hash, None),
+        };
+        Some(Unlock {
+            kind,
+            index,
+            hash,
+            name,
+        })
+    }
+
+    /// Resolves an unlock by its hash.
+    #[tracing::instrument(skip(self))]
+    pub fn resolve_hash(&self, kind: UnlockKind, hash: u32) -> Option<Unlock> {
+        let index = match kind {
+            UnlockKind::Flag => self.flags.index_of(&hash)?,
+            UnlockKind::Value => self.values.index_of(&hash)?,
+        };
+        self.resolve(kind, index)
+    }
+
+    /// Returns all content gated by, or tracked with, the given unlock.
+    #[tracing::instrument(skip(self))]
+    pub fn usages(&self, kind: UnlockKind, index: usize) -> Vec<UnlockUsage> {
+        self.usages
+            .get_or_init(|| self.build_usages())
+            .get(&(kind, index))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    #[tracing::instrument(skip(self))]
+    fn build_usages(&self) -> FxHashMap<(UnlockKind, usize), Vec<UnlockUsage>> {
+        let mut usages: FxHashMap<(UnlockKind, usize), Vec<UnlockUsage>> = FxHashMap::default();
+
+        for (activity_index, act) in investment_manager()
+            .activities()
+            .values()
+            .iter()
+            .enumerate()
+        {
+            let InvestmentData::Activity(act) = act else {
+                continue;
+            };
+            for phase in &act.data.insertion_points.0.phases {
+                usages
+                    .entry((UnlockKind::Flag, phase.unlock_index as usize))
+                    .or_default()
+                    .push(UnlockUsage::InsertionPoint {
+                        activity_index,
+                        phase_hash: phase.phase_hash,
+                    });
+            }
+            for requirement in act.requirements() {
+                usages
+                    .entry((UnlockKind::Flag, requirement.unlock_index as usize))
+                    .or_default()
+                    .push(UnlockUsage::ActivityRequirement { activity_index });
+            }
+        }
+
+        let objectives = investment_manager().objectives();
+        for (objective_index, objective) in objectives.values().iter().enumerate() {
+            if let Ok(value_index) = usize::try_from(objective.unlock_value_index) {
+                usages
+                    .entry((UnlockKind::Value, value_index))
+                    .or_default()
+                    .push(UnlockUsage::Objective { objective_index });
+            }
+        }
+
+        for (item_index, item) in investment_manager().items().values().iter().enumerate() {
+            let InvestmentData::InventoryItem(item) = item else {
+                continue;
+            };
+            for objective in item.objectives() {
+                if let Ok(value_index) = usize::try_from(objective.unlock_value_index) {
+                    usages
+                        .entry((UnlockKind::Value, value_index))
+                        .or_default()
+                        .push(UnlockUsage::Item { item_index });
+                }
+            }
+        }
+
+        usages
+    }
+}
@@ -0,0 +1,172 @@
+use tiger_pkg::{TagHash, TagHash64};
+use tiger_tag::WideHash;
+
+/// Score given to entries found through one of their package tags, ranking them above partial hashes.
+pub const TAG_MATCH_SCORE: u32 = 2000;
+
+/// A whole or partial hash typed into the search bar, matched against the start of an entry's hash.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HashQuery {
+    /// Decimal digits, e.g. `34756`
+    Decimal(String),
+    /// Lowercase hexadecimal digits, typed as `0x1234ab` or bare as `1234ab`
+    Hex(String),
+}
+
+impl HashQuery {
+    /// Returns `None` if `input` doesn't look like a hash.
+    ///
+    /// Digits on their own are read as decimal. Bare hex is only accepted if it mixes digits and
+    /// letters or is a full 8 characters long, so words like "ace" are still searched by name.
+    pub fn parse(input: &str) -> Option<Self> {
+        let input = input.trim();
+        if let Some(hex) = input
+            .strip_prefix("0x")
+            .or_else(|| input.strip_prefix("0X"))
+        {
+            return (is_hex(hex) && hex.len() <= 8).then(|| Self::Hex(hex.to_lowercase()));
+        }
+
+        if input.is_empty() || input.len() > 10 {
+            return None;
+        }
+        if input.chars().all(|c| c.is_ascii_digit()) {
+            return Some(Self::Decimal(input.to_string()));
+        }
+
+        let mixed = input.chars().any(|c| c.is_ascii_digit())
+            && input.chars().any(|c| c.is_ascii_alphabetic());
+        (is_hex(input) && input.len() <= 8 && (mixed || input.len() == 8))
+            .then(|| Self::Hex(input.to_lowercase()))
+    }
+
+    /// Returns true if `input` could just as well be part of a name, like "b52",
+    /// so it should be searched for by name as well as by hash.
+    pub fn could_be_name(input: &str) -> bool {
+        let input = input.trim();
+        !input.starts_with("0x")
+            && !input.starts_with("0X")
+            && input.chars().any(|c| c.is_ascii_alphabetic())
+    }
+
+    /// Scores how well `hash` matches, `None` if it doesn't start with the query.
+    /// Exact matches score highest, followed by the prefixes with the fewest digits left over.
+    pub fn rank(&self, hash: u32) -> Option<u32> {
+        match self {
+            Self::Decimal(d) => rank_prefix(d, &hash.to_string()),
+            // Hex may be typed with or without the leading zeros
+            Self::Hex(h) => {
+                rank_prefix(h, &format!("{hash:x}")).max(rank_prefix(h, &format!("{hash:08x}")))
+            }
+        }
+    }
+}
+
+fn rank_prefix(typed: &str, form: &str) -> Option<u32> {
+    form.starts_with(typed)
+        .then(|| 1000 - (form.len() - typed.len()) as u32)
+}
+
+fn is_hex(s: &str) -> bool {
+    !s.is_empty() && s.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Parses a package tag the way tools print them, e.g. `80A0B1C2`, `TagHash(80A0B1C2)`,
+/// `Hash32(80A0B1C2)` or a 64-bit `Hash64(0123456789ABCDEF)`.
+/// 64-bit hashes are looked up in the package manager, and return `None` if they aren't known.
+pub fn parse_tag_hash(input: &str) -> Option<TagHash> {
+    let mut input = input.trim();
+    for wrapper in ["TagHash64(", "TagHash(", "Hash32(", "Hash64("] {
+        if let Some(inner) = input
+            .strip_prefix(wrapper)
+            .and_then(|i| i.strip_suffix(')'))
+        {
+            input = inner;
+            break;
+        }
+    }
+    let hex = input
+        .strip_prefix("0x")
+        .or_else(|| input.strip_prefix("0X"))
+        .unwrap_or(input);
+    if !is_hex(hex) {
+        return None;
+    }
+
+    // Tags are printed big-endian
+    let hash = match hex.len() {
+        8 => WideHash::Hash32(TagHash(u32::from_be(u32::from_str_radix(hex, 16).ok()?))),
+        16 => WideHash::Hash64(TagHash64(u64::from_be(u64::from_str_radix(hex, 16).ok()?))),
+        _ => return None,
+    };
+    hash.hash32_checked()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(digits: &str) -> HashQuery {
+        HashQuery::Hex(digits.to_string())
+    }
+
+    fn decimal(digits: &str) -> HashQuery {
+        HashQuery::Decimal(digits.to_string())
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(HashQuery::parse("34756"), Some(decimal("34756")));
+        assert_eq!(HashQuery::parse(" 0x1234AB "), Some(hex("1234ab")));
+        assert_eq!(HashQuery::parse("1234ab"), Some(hex("1234ab")));
+        assert_eq!(HashQuery::parse("DEADBEEF"), Some(hex("deadbeef")));
+        assert_eq!(HashQuery::parse("b52"), Some(hex("b52")));
+
+        assert_eq!(HashQuery::parse(""), None);
+        assert_eq!(HashQuery::parse("ace"), None);
+        assert_eq!(HashQuery::parse("0x"), None);
+        assert_eq!(HashQuery::parse("0x123456789"), None);
+        assert_eq!(HashQuery::parse("12345678901"), None);
+        assert_eq!(HashQuery::parse("1234abcde"), None);
+    }
+
+    #[test]
+    fn could_be_name() {
+        assert!(HashQuery::could_be_name("b52"));
+        assert!(HashQuery::could_be_name("deadbeef"));
+        assert!(!HashQuery::could_be_name("0xb52"));
+        assert!(!HashQuery::could_be_name("34756"));
+    }
+
+    #[test]
+    fn rank_prefers_fewer_digits_left_over() {
+        assert_eq!(decimal("123").rank(123), Some(1000));
+        assert_eq!(decimal("123").rank(12345), Some(998));
+        assert_eq!(decimal("123").rank(45123), None);
+
+        assert_eq!(hex("ab12").rank(0xab12), Some(1000));
+        assert_eq!(hex("ab").rank(0xab12), Some(998));
+        assert_eq!(hex("12").rank(0xab12), None);
+    }
+
+    #[test]
+    fn rank_with_leading_zeros() {
+        assert_eq!(hex("0000ab12").rank(0xab12), Some(1000));
+        assert_eq!(hex("00ab").rank(0xab1234), Some(996));
+        assert_eq!(hex("00ab").rank(0xab12), None);
+    }
+
+    #[test]
+    fn tag_hashes() {
+        let tag = Some(TagHash(u32::from_be(0x80A0B1C2)));
+        assert_eq!(parse_tag_hash("80A0B1C2"), tag);
+        assert_eq!(parse_tag_hash("0x80a0b1c2"), tag);
+        assert_eq!(parse_tag_hash("TagHash(80A0B1C2)"), tag);
+        assert_eq!(parse_tag_hash(" Hash32(80A0B1C2) "), tag);
+
+        assert_eq!(parse_tag_hash("FFFFFFFF"), None);
+        assert_eq!(parse_tag_hash("80A0B1"), None);
+        assert_eq!(parse_tag_hash("TagHash(80A0B1C2"), None);
+        assert_eq!(parse_tag_hash("ace of spades"), None);
+    }
+}
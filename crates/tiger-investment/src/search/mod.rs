@@ -0,0 +1,107 @@
+use rustc_hash::FxHashSet;
+
+use crate::{InvestmentData, InvestmentKind, data::character::DestinyClass};
+
+pub mod hash;
+pub mod pipeline;
+pub mod query;
+pub mod rank;
+pub mod text;
+
+/// An entry found by a search, with how well it matched. Higher scores are better matches.
+#[derive(Clone)]
+pub struct SearchResult {
+    pub data: InvestmentData,
+    pub score: u32,
+    /// Generation of the search that found it, see `SearchPipeline`.
+    pub generation: u64,
+}
+
+/// Kinds of entries that searches go through.
+pub const SEARCHABLE_KINDS: [InvestmentKind; 6] = [
+    InvestmentKind::InventoryItem,
+    InvestmentKind::Activity,
+    InvestmentKind::Progression,
+    InvestmentKind::Season,
+    InvestmentKind::Destination,
+    InvestmentKind::Place,
+];
+
+/// Kinds of entries and item categories that are left out of searches. Nothing is by default.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct TypeFilter {
+    pub hidden_kinds: FxHashSet<InvestmentKind>,
+    /// Hashes of root item categories. Items are left out if all of their root categories are.
+    pub hidden_categories: FxHashSet<u32>,
+}
+
+impl TypeFilter {
+    pub fn allows_kind(&self, kind: InvestmentKind) -> bool {
+        !self.hidden_kinds.contains(&kind)
+    }
+
+    pub fn matches(&self, data: &InvestmentData) -> bool {
+        if !self.allows_kind(data.kind()) {
+            return false;
+        }
+
+        if let InvestmentData::InventoryItem(item) = data
+            && !self.hidden_categories.is_empty()
+        {
+            let roots = item.root_categories();
+            return roots.is_empty()
+                || roots
+                    .iter()
+                    .any(|c| !self.hidden_categories.contains(&c.data.hash));
+        }
+
+        true
+    }
+}
+
+/// Facets that narrow down a search, on top of the name.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SearchFilter {
+    /// Only match entries that take place at the destination with this hash.
+    pub destination: Option<u32>,
+    /// Only match gear that is restricted to this class.
+    pub class: Option<DestinyClass>,
+    /// Only match items in the category with this hash, or any category below it.
+    pub category: Option<u32>,
+    pub types: TypeFilter,
+}
+
+impl SearchFilter {
+    pub fn matches(&self, data: &InvestmentData) -> bool {
+        if !self.types.matches(data) {
+            return false;
+        }
+
+        if let Some(destination) = self.destination
+            && data.destination_hash() != Some(destination)
+        {
+            return false;
+        }
+
+        if let Some(class) = self.class {
+            let InvestmentData::InventoryItem(item) = data else {
+                return false;
+            };
+            if item.restriction.class != Some(class) {
+                return false;
+            }
+        }
+
+        if let Some(category) = self.category {
+            let InvestmentData::InventoryItem(item) = data else {
+                return false;
+            };
+            if !item.in_category(category) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
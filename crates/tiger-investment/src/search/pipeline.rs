@@ -0,0 +1,213 @@
+use std::{
+    fmt,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+
+use crossbeam::channel::{Receiver, SendTimeoutError, Sender};
+
+use crate::{InvestmentData, search::SearchResult};
+
+/// How long a blocked search waits for room in the channel before checking if it was cancelled.
+const SEND_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Shared flag telling a running search to stop.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Returned by a search once a newer one has replaced it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SearchCancelled;
+
+impl fmt::Display for SearchCancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Search was cancelled")
+    }
+}
+
+impl std::error::Error for SearchCancelled {}
+
+/// The sending end of one search. Results are tagged with the generation of the search,
+/// so the receiving end can tell them apart from the results of older searches.
+#[derive(Clone, Debug)]
+pub struct SearchSink {
+    generation: u64,
+    cancel: CancellationToken,
+    sender: Sender<SearchResult>,
+    finished: Arc<AtomicU64>,
+}
+
+impl SearchSink {
+    /// Returns an error once the search has been cancelled, so it can stop early.
+    pub fn check(&self) -> Result<(), SearchCancelled> {
+        if self.cancel.is_cancelled() {
+            Err(SearchCancelled)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Sends a result, waiting for room in the channel rather than dropping it.
+    pub fn send(&self, data: InvestmentData, score: u32) -> Result<(), SearchCancelled> {
+        let mut result = SearchResult {
+            data,
+            score,
+            generation: self.generation,
+        };
+        loop {
+            self.check()?;
+            match self.sender.send_timeout(result, SEND_POLL_INTERVAL) {
+                Ok(()) => return Ok(()),
+                Err(SendTimeoutError::Timeout(r)) => result = r,
+                Err(SendTimeoutError::Disconnected(_)) => return Err(SearchCancelled),
+            }
+        }
+    }
+
+    /// Marks the search as done, once every result has been sent.
+    pub fn finish(&self) {
+        self.finished.fetch_max(self.generation, Ordering::Relaxed);
+    }
+}
+
+/// Both ends of the search channel. Starting a search cancels the one before it.
+pub struct SearchPipeline {
+    sender: Sender<SearchResult>,
+    receiver: Receiver<SearchResult>,
+    generation: u64,
+    cancel: CancellationToken,
+    finished: Arc<AtomicU64>,
+}
+
+impl SearchPipeline {
+    /// `capacity` is how many results can wait in the channel before searches block.
+    pub fn new(capacity: usize) -> Self {
+        let (sender, receiver) = crossbeam::channel::bounded(capacity);
+        Self {
+            sender,
+            receiver,
+            generation: 0,
+            cancel: CancellationToken::default(),
+            finished: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Cancels the running search, and returns the sink for a new one.
+    pub fn restart(&mut self) -> SearchSink {
+        self.cancel();
+        self.generation += 1;
+        self.cancel = CancellationToken::default();
+        SearchSink {
+            generation: self.generation,
+            cancel: self.cancel.clone(),
+            sender: self.sender.clone(),
+            finished: self.finished.clone(),
+        }
+    }
+
+    /// Cancels the running search, without starting a new one.
+    pub fn cancel(&self) {
+        self.cancel.cancel();
+        self.finished.fetch_max(self.generation, Ordering::Relaxed);
+    }
+
+    /// Generation of the current search, counting up from 1.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Returns true once the current search has sent all of its results.
+    /// There may still be some waiting to be received.
+    pub fn is_finished(&self) -> bool {
+        self.finished.load(Ordering::Relaxed) >= self.generation
+    }
+
+    /// Returns the next result of the current search, throwing away any left over from older ones.
+    pub fn try_recv(&self) -> Option<SearchResult> {
+        while let Ok(result) = self.receiver.try_recv() {
+            if result.generation == self.generation {
+                return Some(result);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry() -> InvestmentData {
+        InvestmentData::Achievement
+    }
+
+    #[test]
+    fn restart_cancels_the_previous_search() {
+        let mut pipeline = SearchPipeline::new(8);
+        let old = pipeline.restart();
+        let new = pipeline.restart();
+
+        assert_eq!(old.check(), Err(SearchCancelled));
+        assert_eq!(old.send(entry(), 0), Err(SearchCancelled));
+        assert_eq!(new.check(), Ok(()));
+        assert_eq!(new.send(entry(), 0), Ok(()));
+    }
+
+    #[test]
+    fn stale_results_are_thrown_away() {
+        let mut pipeline = SearchPipeline::new(8);
+        let old = pipeline.restart();
+        old.send(entry(), 1).unwrap();
+        old.send(entry(), 1).unwrap();
+
+        let new = pipeline.restart();
+        new.send(entry(), 2).unwrap();
+
+        let result = pipeline.try_recv().unwrap();
+        assert_eq!(result.score, 2);
+        assert_eq!(result.generation, pipeline.generation());
+        assert!(pipeline.try_recv().is_none());
+    }
+
+    #[test]
+    fn finished_once_the_current_search_is() {
+        let mut pipeline = SearchPipeline::new(8);
+        let old = pipeline.restart();
+        assert!(!pipeline.is_finished());
+
+        let new = pipeline.restart();
+        old.finish();
+        assert!(!pipeline.is_finished());
+        new.finish();
+        assert!(pipeline.is_finished());
+
+        pipeline.restart();
+        assert!(!pipeline.is_finished());
+        pipeline.cancel();
+        assert!(pipeline.is_finished());
+    }
+
+    #[test]
+    fn blocked_send_stops_when_cancelled() {
+        let mut pipeline = SearchPipeline::new(1);
+        let sink = pipeline.restart();
+        sink.send(entry(), 0).unwrap();
+
+        let blocked = std::thread::spawn(move || sink.send(entry(), 0));
+        pipeline.cancel();
+        assert_eq!(blocked.join().unwrap(), Err(SearchCancelled));
+    }
+}
@@ -0,0 +1,466 @@
+use std::fmt;
+
+use strum::IntoEnumIterator;
+
+use crate::{InvestmentData, InvestmentKind, data::item::ItemTier, search::text::TextField};
+
+/// A compiled search query, e.g. `type:item name:"ace of" (tier:exotic OR tier:legendary) NOT hash:0x1234`.
+///
+/// Terms next to each other are combined with AND, which binds tighter than OR.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Query {
+    And(Box<Query>, Box<Query>),
+    Or(Box<Query>, Box<Query>),
+    Not(Box<Query>),
+    Kind(InvestmentKind),
+    /// Case-insensitive substring of the entry's name
+    Name(String),
+    /// Case-insensitive substring of one of the entry's text fields
+    Text(TextField, String),
+    Tier(ItemTier),
+    Hash(u32),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct QueryError {
+    /// Character offset in the input the error was found at.
+    pub position: usize,
+    pub message: String,
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "col {}: {}", self.position + 1, self.message)
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+impl Query {
+    /// Returns true if `input` uses a known `field:` or an AND, OR or NOT operator,
+    /// rather than being a plain name like "Nightfall: The Ordeal".
+    pub fn is_query(input: &str) -> bool {
+        input
+            .split(|c: char| c.is_whitespace() || matches!(c, '(' | ')'))
+            .any(|word| {
+                matches!(word, "AND" | "OR" | "NOT")
+                    || word
+                        .split_once(':')
+                        .is_some_and(|(field, _)| is_field(field))
+            })
+    }
+
+    pub fn parse(input: &str) -> Result<Self, QueryError> {
+        let tokens = lex(input)?;
+        let mut parser = Parser {
+            tokens,
+            position: 0,
+            end: input.chars().count(),
+        };
+        let query = parser.or_expr()?;
+        match parser.peek() {
+            None => Ok(query),
+            Some((pos, _)) => Err(QueryError {
+                position: pos,
+                message: "Unexpected ')'".to_string(),
+            }),
+        }
+    }
+
+    pub fn matches(&self, data: &InvestmentData) -> bool {
+        match self {
+            Self::And(a, b) => a.matches(data) && b.matches(data),
+            Self::Or(a, b) => a.matches(data) || b.matches(data),
+            Self::Not(q) => !q.matches(data),
+            Self::Kind(kind) => data.kind() == *kind,
+            Self::Name(name) => data.name().to_lowercase().contains(name),
+            Self::Text(field, text) => TextField::of(data).iter().any(|(f, s)| {
+                f == field
+                    && s.get()
+                        .is_some_and(|s| s.to_lowercase().contains(text.as_str()))
+            }),
+            Self::Tier(tier) => match data {
+                InvestmentData::InventoryItem(i) => i.tier() == Some(*tier),
+                _ => false,
+            },
+            Self::Hash(hash) => data.hash() == *hash,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    LParen,
+    RParen,
+    And,
+    Or,
+    Not,
+    Term {
+        field: Option<String>,
+        value: String,
+    },
+}
+
+/// A word of a query, before any operators are picked out of the words.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Lexeme {
+    LParen,
+    RParen,
+    Word {
+        field: Option<String>,
+        value: String,
+        quoted: bool,
+    },
+}
+
+/// Splits a query into parentheses and words, with the `field:` each word is prefixed with.
+/// Also used for full-text queries, which share the syntax for fields and quoted phrases.
+pub(crate) fn scan(input: &str) -> Result<Vec<(usize, Lexeme)>, QueryError> {
+    let mut lexemes = Vec::new();
+    let mut chars = input.chars().enumerate().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '(' => lexemes.push((start, Lexeme::LParen)),
+            ')' => lexemes.push((start, Lexeme::RParen)),
+            _ => {
+                let mut word = String::new();
+                let mut field = None;
+                let mut quoted = false;
+                let mut c = Some(c);
+                while let Some(ch) = c {
+                    match ch {
+                        '"' => {
+                            quoted = true;
+                            let mut closed = false;
+                            for (_, q) in chars.by_ref() {
+                                if q == '"' {
+                                    closed = true;
+                                    break;
+                                }
+                                word.push(q);
+                            }
+                            if !closed {
+                                return Err(QueryError {
+                                    position: start,
+                                    message: "Unterminated quote".to_string(),
+                                });
+                            }
+                        }
+                        ':' if field.is_none() => field = Some(std::mem::take(&mut word)),
+                        _ => word.push(ch),
+                    }
+                    c = chars
+                        .next_if(|(_, n)| !n.is_whitespace() && !matches!(n, '(' | ')'))
+                        .map(|(_, n)| n);
+                }
+
+                lexemes.push((
+                    start,
+                    Lexeme::Word {
+                        field,
+                        value: word,
+                        quoted,
+                    },
+                ));
+            }
+        }
+    }
+
+    Ok(lexemes)
+}
+
+fn lex(input: &str) -> Result<Vec<(usize, Token)>, QueryError> {
+    let tokens = scan(input)?
+        .into_iter()
+        .map(|(position, lexeme)| {
+            let token = match lexeme {
+                Lexeme::LParen => Token::LParen,
+                Lexeme::RParen => Token::RParen,
+                // A quoted "OR" is a term, not an operator
+                Lexeme::Word {
+                    field,
+                    value,
+                    quoted,
+                } => match (field, value.as_str(), quoted) {
+                    (None, "AND", false) => Token::And,
+                    (None, "OR", false) => Token::Or,
+                    (None, "NOT", false) => Token::Not,
+                    (field, _, _) => Token::Term { field, value },
+                },
+            };
+            (position, token)
+        })
+        .collect();
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    position: usize,
+    /// Length of the input, to point errors at when the query ends too early
+    end: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<(usize, &Token)> {
+        self.tokens.get(self.position).map(|(p, t)| (*p, t))
+    }
+
+    fn advance(&mut self) -> Option<(usize, Token)> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn or_expr(&mut self) -> Result<Query, QueryError> {
+        let mut query = self.and_expr()?;
+        while let Some((_, Token::Or)) = self.peek() {
+            self.advance();
+            query = Query::Or(Box::new(query), Box::new(self.and_expr()?));
+        }
+        Ok(query)
+    }
+
+    fn and_expr(&mut self) -> Result<Query, QueryError> {
+        let mut query = self.not_expr()?;
+        loop {
+            match self.peek() {
+                Some((_, Token::And)) => {
+                    self.advance();
+                }
+                Some((_, Token::Not | Token::LParen | Token::Term { .. })) => {}
+                _ => break,
+            }
+            query = Query::And(Box::new(query), Box::new(self.not_expr()?));
+        }
+        Ok(query)
+    }
+
+    fn not_expr(&mut self) -> Result<Query, QueryError> {
+        if let Some((_, Token::Not)) = self.peek() {
+            self.advance();
+            return Ok(Query::Not(Box::new(self.not_expr()?)));
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Query, QueryError> {
+        match self.advance() {
+            Some((_, Token::LParen)) => {
+                let query = self.or_expr()?;
+                match self.advance() {
+                    Some((_, Token::RParen)) => Ok(query),
+                    other => Err(QueryError {
+                        position: other.map(|(p, _)| p).unwrap_or(self.end),
+                        message: "Expected ')'".to_string(),
+                    }),
+                }
+            }
+            Some((position, Token::Term { field, value })) => {
+                term(field.as_deref(), &value).map_err(|message| QueryError { position, message })
+            }
+            Some((position, token)) => Err(QueryError {
+                position,
+                message: format!("Expected a search term, found {}", token_name(&token)),
+            }),
+            None => Err(QueryError {
+                position: self.end,
+                message: "Expected a search term".to_string(),
+            }),
+        }
+    }
+}
+
+fn token_name(token: &Token) -> &'static str {
+    match token {
+        Token::LParen => "'('",
+        Token::RParen => "')'",
+        Token::And => "AND",
+        Token::Or => "OR",
+        Token::Not => "NOT",
+        Token::Term { .. } => "a term",
+    }
+}
+
+/// Returns true if `field` is one a term can be prefixed with.
+fn is_field(field: &str) -> bool {
+    matches!(
+        field.to_lowercase().as_str(),
+        "type" | "name" | "tier" | "hash"
+    ) || TextField::lookup(field).is_some()
+}
+
+fn term(field: Option<&str>, value: &str) -> Result<Query, String> {
+    let Some(field) = field else {
+        return Ok(Query::Name(value.to_lowercase()));
+    };
+    if value.is_empty() {
+        return Err(format!("Missing value for '{field}:'"));
+    }
+
+    match field.to_lowercase().as_str() {
+        "type" => parse_kind(value)
+            .map(Query::Kind)
+            .ok_or_else(|| format!("Unknown type '{value}'")),
+        "name" => Ok(Query::Name(value.to_lowercase())),
+        "tier" => value
+            .parse::<ItemTier>()
+            .map(Query::Tier)
+            .map_err(|_| format!("Unknown tier '{value}'")),
+        "hash" => parse_hash(value)
+            .map(Query::Hash)
+            .ok_or_else(|| format!("Invalid hash '{value}'")),
+        _ => TextField::lookup(field)
+            .map(|f| Query::Text(f, value.to_lowercase()))
+            .ok_or_else(|| format!("Unknown field '{field}'")),
+    }
+}
+
+fn parse_kind(value: &str) -> Option<InvestmentKind> {
+    if value.eq_ignore_ascii_case("item") {
+        return Some(InvestmentKind::InventoryItem);
+    }
+    InvestmentKind::iter().find(|k| k.to_string().eq_ignore_ascii_case(value))
+}
+
+/// Parses a decimal hash, or a hexadecimal one prefixed with 0x.
+pub fn parse_hash(value: &str) -> Option<u32> {
+    match value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+    {
+        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+        None => value.parse().ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn name(value: &str) -> Query {
+        Query::Name(value.to_string())
+    }
+
+    fn and(a: Query, b: Query) -> Query {
+        Query::And(Box::new(a), Box::new(b))
+    }
+
+    fn or(a: Query, b: Query) -> Query {
+        Query::Or(Box::new(a), Box::new(b))
+    }
+
+    fn not(q: Query) -> Query {
+        Query::Not(Box::new(q))
+    }
+
+    fn error(input: &str) -> (usize, String) {
+        let e = Query::parse(input).unwrap_err();
+        (e.position, e.message)
+    }
+
+    #[test]
+    fn and_binds_tighter_than_or() {
+        assert_eq!(
+            Query::parse("a b OR c"),
+            Ok(or(and(name("a"), name("b")), name("c")))
+        );
+        assert_eq!(
+            Query::parse("a OR b AND c"),
+            Ok(or(name("a"), and(name("b"), name("c"))))
+        );
+        assert_eq!(
+            Query::parse("(a OR b) c"),
+            Ok(and(or(name("a"), name("b")), name("c")))
+        );
+    }
+
+    #[test]
+    fn not_applies_to_the_next_term() {
+        assert_eq!(Query::parse("NOT a b"), Ok(and(not(name("a")), name("b"))));
+        assert_eq!(
+            Query::parse("NOT (a OR b)"),
+            Ok(not(or(name("a"), name("b"))))
+        );
+        assert_eq!(Query::parse("NOT NOT a"), Ok(not(not(name("a")))));
+    }
+
+    #[test]
+    fn quoted_values() {
+        assert_eq!(
+            Query::parse(r#"name:"Ace of Spades""#),
+            Ok(name("ace of spades"))
+        );
+        assert_eq!(
+            Query::parse(r#"a "OR" b"#),
+            Ok(and(and(name("a"), name("or")), name("b")))
+        );
+        assert_eq!(Query::parse(r#""NOT""#), Ok(name("not")));
+    }
+
+    #[test]
+    fn fields() {
+        assert_eq!(
+            Query::parse("type:item"),
+            Ok(Query::Kind(InvestmentKind::InventoryItem))
+        );
+        assert_eq!(
+            Query::parse("Tier:exotic"),
+            Ok(Query::Tier(ItemTier::Exotic))
+        );
+        assert_eq!(
+            Query::parse("flavor:Light"),
+            Ok(Query::Text(TextField::Flavor, "light".to_string()))
+        );
+    }
+
+    #[test]
+    fn hashes() {
+        assert_eq!(Query::parse("hash:0x1234ABCD"), Ok(Query::Hash(0x1234ABCD)));
+        assert_eq!(Query::parse("hash:3588934839"), Ok(Query::Hash(3588934839)));
+        assert_eq!(parse_hash("0X1f"), Some(0x1f));
+        assert_eq!(parse_hash("0x"), None);
+        assert_eq!(parse_hash("12ab"), None);
+        assert_eq!(parse_hash("4294967296"), None);
+    }
+
+    #[test]
+    fn error_columns() {
+        assert_eq!(error("type:nope"), (0, "Unknown type 'nope'".to_string()));
+        assert_eq!(
+            error("a colour:red"),
+            (2, "Unknown field 'colour'".to_string())
+        );
+        assert_eq!(
+            error("a name:"),
+            (2, "Missing value for 'name:'".to_string())
+        );
+        assert_eq!(error(r#"a "bc"#), (2, "Unterminated quote".to_string()));
+        assert_eq!(error("a (b"), (4, "Expected ')'".to_string()));
+        assert_eq!(error("a )"), (2, "Unexpected ')'".to_string()));
+        assert_eq!(error("a OR"), (4, "Expected a search term".to_string()));
+        assert_eq!(
+            error("a OR OR b"),
+            (5, "Expected a search term, found OR".to_string())
+        );
+        assert_eq!(
+            Query::parse("hash:xyz").unwrap_err().to_string(),
+            "col 1: Invalid hash 'xyz'"
+        );
+    }
+
+    #[test]
+    fn plain_names_are_not_queries() {
+        assert!(Query::is_query("type:item"));
+        assert!(Query::is_query("(tier:exotic OR tier:legendary)"));
+        assert!(Query::is_query("NOT(hash:0x1234)"));
+        assert!(Query::is_query("ace OR spades"));
+
+        assert!(!Query::is_query("Nightfall: The Ordeal"));
+        assert!(!Query::is_query("Hawkmoon (Adept)"));
+        assert!(!Query::is_query(r#""Ace of Spades""#));
+        assert!(!Query::is_query("rock or roll"));
+    }
+}
@@ -0,0 +1,212 @@
+/// How well a name matches a search, from worst to best.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MatchKind {
+    /// Every word of the search is close to a word of the name, allowing for typos.
+    Fuzzy,
+    /// The search appears somewhere in the name.
+    Substring,
+    /// A word of the name starts with the search.
+    WordPrefix,
+    /// The name starts with the search.
+    Prefix,
+    Exact,
+}
+
+/// Scores how well `name` matches `query`, `None` if it doesn't match at all. Higher is better.
+///
+/// Matches are ranked by their `MatchKind` first, then by how close the lengths of the two are.
+/// `query` is expected to be lowercase already.
+pub fn rank(query: &str, name: &str) -> Option<u32> {
+    if query.is_empty() {
+        // Everything matches an empty search equally, so it lists the whole table
+        return Some(0);
+    }
+    let name = name.to_lowercase();
+    let (kind, penalty) = match_kind(query, &name)?;
+    let closeness = 999u32.saturating_sub(penalty);
+    Some((kind as u32 + 1) * 1000 + closeness)
+}
+
+/// Like `rank`, but entries without a name only match an empty search.
+pub fn rank_name(query: &str, name: Option<&str>) -> Option<u32> {
+    match name {
+        Some(name) => rank(query, name),
+        None => query.is_empty().then_some(0),
+    }
+}
+
+fn match_kind(query: &str, name: &str) -> Option<(MatchKind, u32)> {
+    let length_penalty = name.chars().count().abs_diff(query.chars().count()) as u32;
+
+    if name == query {
+        return Some((MatchKind::Exact, 0));
+    }
+    if name.starts_with(query) {
+        return Some((MatchKind::Prefix, length_penalty));
+    }
+    let mut positions = name
+        .match_indices(query)
+        .map(|(position, _)| position)
+        .peekable();
+    if positions.peek().is_some() {
+        let at_word_start = positions.any(|position| {
+            name[..position]
+                .chars()
+                .last()
+                .is_some_and(|c| !c.is_alphanumeric())
+        });
+        if at_word_start {
+            return Some((MatchKind::WordPrefix, length_penalty));
+        }
+        return Some((MatchKind::Substring, length_penalty));
+    }
+
+    fuzzy_distance(query, name).map(|distance| (MatchKind::Fuzzy, distance * 100 + length_penalty))
+}
+
+/// Matches every word of the query against the words of the name, allowing a few typos per word.
+/// The last word of the query may also be an unfinished prefix. Returns the total number of typos.
+fn fuzzy_distance(query: &str, name: &str) -> Option<u32> {
+    let query_words: Vec<Vec<char>> = words(query).collect();
+    let name_words: Vec<Vec<char>> = words(name).collect();
+    if query_words.is_empty() {
+        return None;
+    }
+
+    let mut total = 0;
+    for (i, query_word) in query_words.iter().enumerate() {
+        let is_last = i == query_words.len() - 1;
+        let allowed = allowed_typos(query_word.len());
+        total += name_words
+            .iter()
+            .filter_map(|name_word| {
+                let distance = if is_last && name_word.len() > query_word.len() {
+                    // Compare against the start of the word too, for words that are still being typed
+                    edit_distance(query_word, &name_word[..query_word.len()])
+                        .min(edit_distance(query_word, name_word))
+                } else {
+                    edit_distance(query_word, name_word)
+                };
+                (distance <= allowed).then_some(distance)
+            })
+            .min()?;
+    }
+
+    Some(total)
+}
+
+fn words(text: &str) -> impl Iterator<Item = Vec<char>> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| w.chars().collect())
+}
+
+fn allowed_typos(word_length: usize) -> u32 {
+    match word_length {
+        0..=3 => 0,
+        4..=7 => 1,
+        _ => 2,
+    }
+}
+
+/// Optimal string alignment distance, counting a swap of two neighbouring characters as a single typo.
+fn edit_distance(a: &[char], b: &[char]) -> u32 {
+    let width = b.len() + 1;
+    let mut rows = vec![0u32; (a.len() + 1) * width];
+    for (j, cell) in rows.iter_mut().take(width).enumerate() {
+        *cell = j as u32;
+    }
+
+    for i in 1..=a.len() {
+        rows[i * width] = i as u32;
+        for j in 1..=b.len() {
+            let cost = u32::from(a[i - 1] != b[j - 1]);
+            let mut distance = (rows[(i - 1) * width + j] + 1)
+                .min(rows[i * width + j - 1] + 1)
+                .min(rows[(i - 1) * width + j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(rows[(i - 2) * width + j - 2] + 1);
+            }
+            rows[i * width + j] = distance;
+        }
+    }
+
+    rows[a.len() * width + b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chars(s: &str) -> Vec<char> {
+        s.chars().collect()
+    }
+
+    fn kind(query: &str, name: &str) -> Option<MatchKind> {
+        match_kind(query, &name.to_lowercase()).map(|(kind, _)| kind)
+    }
+
+    #[test]
+    fn match_kinds_are_ranked_in_order() {
+        assert_eq!(kind("ace", "Ace"), Some(MatchKind::Exact));
+        assert_eq!(kind("ace", "Ace of Spades"), Some(MatchKind::Prefix));
+        assert_eq!(kind("spades", "Ace of Spades"), Some(MatchKind::WordPrefix));
+        assert_eq!(kind("ace", "Grace"), Some(MatchKind::Substring));
+        assert_eq!(
+            kind("ace of spdaes", "Ace of Spades"),
+            Some(MatchKind::Fuzzy)
+        );
+
+        let ranks = [
+            rank("spades", "Spades"),
+            rank("spades", "Spades of Aces"),
+            rank("spades", "Ace of Spades"),
+            rank("spades", "Aspades"),
+            rank("spades", "Spdaes"),
+        ];
+        assert!(ranks.iter().all(Option::is_some));
+        assert!(ranks.windows(2).all(|pair| pair[0] > pair[1]));
+    }
+
+    #[test]
+    fn closer_lengths_rank_higher() {
+        assert!(rank("ace", "Ace of Spades") > rank("ace", "Ace of Spades (Adept)"));
+        assert!(rank("ace", "Grace") > rank("ace", "Disgraced"));
+    }
+
+    #[test]
+    fn typos() {
+        assert!(rank("ace of spdaes", "Ace of Spades").is_some());
+        assert!(rank("ace of spdae", "Ace of Spades").is_some());
+        assert!(rank("hawkmon", "Hawkmoon").is_some());
+        // Short words must match exactly
+        assert_eq!(rank("axe", "Ace"), None);
+        assert_eq!(rank("ace of spxdxs", "Ace of Spades"), None);
+    }
+
+    #[test]
+    fn typo_thresholds() {
+        assert_eq!(allowed_typos(3), 0);
+        assert_eq!(allowed_typos(4), 1);
+        assert_eq!(allowed_typos(7), 1);
+        assert_eq!(allowed_typos(8), 2);
+    }
+
+    #[test]
+    fn optimal_string_alignment_distance() {
+        assert_eq!(edit_distance(&chars("spades"), &chars("spades")), 0);
+        assert_eq!(edit_distance(&chars(""), &chars("ace")), 3);
+        assert_eq!(edit_distance(&chars("kitten"), &chars("sitting")), 3);
+        // A swap of neighbours is one typo
+        assert_eq!(edit_distance(&chars("spdaes"), &chars("spades")), 1);
+        // Unlike full Damerau-Levenshtein, swapped characters aren't edited again
+        assert_eq!(edit_distance(&chars("ca"), &chars("abc")), 3);
+    }
+
+    #[test]
+    fn unnamed_entries_only_match_an_empty_search() {
+        assert_eq!(rank_name("", None), Some(0));
+        assert_eq!(rank_name("ace", None), None);
+        assert_eq!(rank_name("", Some("Ace")), Some(0));
+    }
+}
@@ -0,0 +1,374 @@
+use std::{collections::BTreeMap, ops::Bound};
+
+use rustc_hash::FxHashMap;
+use tiger_text::Language;
+
+use crate::{
+    EntryRef, InvestmentData,
+    data::text::IndexedString,
+    global_instance::investment_manager,
+    search::{
+        pipeline::{SearchCancelled, SearchSink},
+        query::{Lexeme, QueryError, scan},
+    },
+};
+
+/// How many entries are indexed between checks for cancellation.
+const CANCEL_CHECK_INTERVAL: usize = 1024;
+
+/// A text field of an entry that is covered by the full-text index.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Hash, strum::Display, strum::EnumString, strum::EnumIter,
+)]
+#[strum(serialize_all = "snake_case")]
+pub enum TextField {
+    Name,
+    ItemType,
+    Description,
+    Flavor,
+    Toast,
+    DisplaySource,
+}
+
+impl TextField {
+    /// Looks up a field by the name it's typed as in a `field:` prefix, ignoring case.
+    pub fn lookup(name: &str) -> Option<Self> {
+        name.to_lowercase().parse().ok()
+    }
+
+    /// How much a match in this field counts towards an entry's score.
+    fn weight(self) -> u32 {
+        match self {
+            Self::Name => 4,
+            Self::ItemType => 3,
+            Self::Description | Self::Flavor => 2,
+            Self::Toast | Self::DisplaySource => 1,
+        }
+    }
+
+    /// Returns the indexed text fields of an entry.
+    pub fn of(entry: &InvestmentData) -> Vec<(TextField, IndexedString)> {
+        match entry {
+            InvestmentData::InventoryItem(i) => vec![
+                (Self::Name, i.display.name.clone()),
+                (Self::ItemType, i.display.item_type.clone()),
+                (Self::Flavor, i.display.flavor.clone()),
+                (Self::Toast, i.display.toast.clone()),
+                (Self::DisplaySource, i.display.display_source.clone()),
+            ],
+            InvestmentData::Activity(a) => vec![
+                (Self::Name, a.display.display_properties.name.clone()),
+                (
+                    Self::Description,
+                    a.display.display_properties.description.clone(),
+                ),
+            ],
+            InvestmentData::Progression(p) => vec![
+                (Self::Name, p.display.name.clone()),
+                (Self::Description, p.display.description.clone()),
+            ],
+            InvestmentData::Season(s) => vec![
+                (Self::Name, s.data.name.clone()),
+                (Self::Description, s.data.description.clone()),
+            ],
+            InvestmentData::Destination(d) => vec![
+                (Self::Name, d.data.name.clone()),
+                (Self::Description, d.data.description.clone()),
+            ],
+            InvestmentData::Place(p) => vec![
+                (Self::Name, p.data.name.clone()),
+                (Self::Description, p.data.description.clone()),
+            ],
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// A full-text query. Every term has to match for an entry to match.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TextQuery {
+    pub terms: Vec<TextTerm>,
+}
+
+/// Words that have to appear in an entry, optionally in a specific field, e.g. `flavor:"eyes up"`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextTerm {
+    pub field: Option<TextField>,
+    pub words: Vec<String>,
+}
+
+impl TextQuery {
+    /// Parses a query made up of words, quoted phrases and `field:` scoped terms, in the same
+    /// syntax as `Query`. A prefix that isn't a known field is searched for as a word.
+    pub fn parse(input: &str) -> Result<Self, QueryError> {
+        let mut terms = Vec::new();
+        for (_, lexeme) in scan(input)? {
+            // There's no grouping in full-text queries, so parentheses are left out
+            let Lexeme::Word { field, value, .. } = lexeme else {
+                continue;
+            };
+            let (field, text) = match field {
+                None => (None, value),
+                Some(prefix) => match TextField::lookup(&prefix) {
+                    Some(field) => (Some(field), value),
+                    None => (None, format!("{prefix} {value}")),
+                },
+            };
+
+            let words: Vec<String> = tokenize(&text).collect();
+            if !words.is_empty() {
+                terms.push(TextTerm { field, words });
+            }
+        }
+
+        Ok(Self { terms })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+}
+
+/// Splits text into lowercase words.
+pub fn tokenize(text: &str) -> impl Iterator<Item = String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_lowercase())
+}
+
+#[derive(Clone, Copy)]
+struct Posting {
+    entry: u32,
+    field: TextField,
+}
+
+/// Inverted index from words to the entries and fields they appear in.
+pub struct TextIndex {
+    language: Language,
+    entries: Vec<EntryRef>,
+    words: BTreeMap<String, Vec<Posting>>,
+}
+
+impl TextIndex {
+    fn new(language: Language) -> Self {
+        Self {
+            language,
+            entries: Vec::new(),
+            words: BTreeMap::new(),
+        }
+    }
+
+    /// Indexes the text fields of every table, in the current language.
+    /// Stops early if the search waiting on it is cancelled.
+    #[tracing::instrument(skip(sink))]
+    pub fn build(sink: &SearchSink) -> Result<Self, SearchCancelled> {
+        let im = investment_manager();
+        let mut index = Self::new(im.strings().lang());
+
+        for (i, entry) in im
+            .items()
+            .values()
+            .iter()
+            .chain(im.activities().values())
+            .chain(im.progressions().values())
+            .chain(im.seasons().values())
+            .chain(im.destinations().values())
+            .enumerate()
+        {
+            if i % CANCEL_CHECK_INTERVAL == 0 {
+                sink.check()?;
+            }
+            index.add_entry(
+                entry.entry_ref(),
+                TextField::of(entry)
+                    .into_iter()
+                    .filter_map(|(field, string)| Some((field, string.get()?))),
+            );
+        }
+
+        Ok(index)
+    }
+
+    fn add_entry(&mut self, entry: EntryRef, fields: impl Iterator<Item = (TextField, String)>) {
+        let id = self.entries.len() as u32;
+        self.entries.push(entry);
+
+        for (field, text) in fields {
+            for word in tokenize(&text) {
+                let postings = self.words.entry(word).or_default();
+                if !postings
+                    .last()
+                    .is_some_and(|p| p.entry == id && p.field == field)
+                {
+                    postings.push(Posting { entry: id, field });
+                }
+            }
+        }
+    }
+
+    /// The language the index was built with.
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    /// Returns every entry matching all terms of `query` with its score, best match first.
+    /// Words match any indexed word they are a prefix of, but whole words and matches in the
+    /// name score higher. Entries with the same score are in table order.
+    #[tracing::instrument(skip(self))]
+    pub fn search(&self, query: &TextQuery) -> Vec<(EntryRef, u32)> {
+        let mut matches: Option<FxHashMap<u32, u32>> = None;
+
+        for term in &query.terms {
+            for word in &term.words {
+                // Best score of the word for each entry it's found in
+                let mut found: FxHashMap<u32, u32> = FxHashMap::default();
+                for (indexed, postings) in self
+                    .words
+                    .range::<str, _>((Bound::Included(word.as_str()), Bound::Unbounded))
+                    .take_while(|(w, _)| w.starts_with(word.as_str()))
+                {
+                    let exact = if indexed == word { 2 } else { 1 };
+                    for posting in postings {
+                        if term.field.is_none_or(|f| f == posting.field) {
+                            let score = found.entry(posting.entry).or_default();
+                            *score = (*score).max(posting.field.weight() * exact);
+                        }
+                    }
+                }
+
+                matches = Some(match matches {
+                    Some(m) => m
+                        .into_iter()
+                        .filter_map(|(id, score)| Some((id, score + found.get(&id)?)))
+                        .collect(),
+                    None => found,
+                });
+            }
+        }
+
+        let mut ids: Vec<(u32, u32)> = matches.unwrap_or_default().into_iter().collect();
+        ids.sort_unstable_by(|(a, a_score), (b, b_score)| b_score.cmp(a_score).then(a.cmp(b)));
+        ids.into_iter()
+            .map(|(id, score)| (self.entries[id as usize], score))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InvestmentKind;
+
+    fn term(field: Option<TextField>, words: &[&str]) -> TextTerm {
+        TextTerm {
+            field,
+            words: words.iter().map(|w| w.to_string()).collect(),
+        }
+    }
+
+    fn parse(input: &str) -> Vec<TextTerm> {
+        TextQuery::parse(input).unwrap().terms
+    }
+
+    #[test]
+    fn parse_words_and_phrases() {
+        assert_eq!(
+            parse("eyes  UP"),
+            [term(None, &["eyes"]), term(None, &["up"])]
+        );
+        assert_eq!(
+            parse(r#"flavor:"Eyes up, Guardian""#),
+            [term(Some(TextField::Flavor), &["eyes", "up", "guardian"])]
+        );
+        assert_eq!(parse(r#"a "OR" (b)"#).len(), 3);
+        assert!(TextQuery::parse("  ").unwrap().is_empty());
+    }
+
+    #[test]
+    fn parse_field_prefixes() {
+        assert_eq!(
+            parse("Item_Type:rifle name:ace"),
+            [
+                term(Some(TextField::ItemType), &["rifle"]),
+                term(Some(TextField::Name), &["ace"])
+            ]
+        );
+        assert_eq!(parse("foo:bar"), [term(None, &["foo", "bar"])]);
+        assert_eq!(parse("flavor:"), []);
+    }
+
+    #[test]
+    fn parse_unterminated_quote() {
+        let error = TextQuery::parse(r#"eyes "up"#).unwrap_err();
+        assert_eq!(error.position, 5);
+    }
+
+    fn index() -> TextIndex {
+        let mut index = TextIndex::new(Language::English);
+        for (hash, fields) in [
+            (
+                1,
+                [
+                    (TextField::Name, "Ace of Spades"),
+                    (TextField::Flavor, "Eyes up, Guardian"),
+                ],
+            ),
+            (
+                2,
+                [
+                    (TextField::Name, "Spade"),
+                    (TextField::Description, "Ace in the hole"),
+                ],
+            ),
+            (
+                3,
+                [
+                    (TextField::Name, "Aces High"),
+                    (TextField::Toast, "Guardian down"),
+                ],
+            ),
+        ] {
+            index.add_entry(
+                EntryRef {
+                    kind: InvestmentKind::InventoryItem,
+                    hash,
+                },
+                fields.into_iter().map(|(f, text)| (f, text.to_string())),
+            );
+        }
+        index
+    }
+
+    fn search(index: &TextIndex, query: &str) -> Vec<(u32, u32)> {
+        index
+            .search(&TextQuery::parse(query).unwrap())
+            .into_iter()
+            .map(|(entry, score)| (entry.hash, score))
+            .collect()
+    }
+
+    #[test]
+    fn search_ranks_whole_words_and_names_first() {
+        let index = index();
+        assert_eq!(search(&index, "ace"), [(1, 8), (2, 4), (3, 4)]);
+        assert_eq!(search(&index, "spade"), [(2, 8), (1, 4)]);
+        assert_eq!(search(&index, "guardian"), [(1, 4), (3, 2)]);
+        assert_eq!(search(&index, "zzz"), []);
+    }
+
+    #[test]
+    fn search_needs_every_word() {
+        let index = index();
+        assert_eq!(search(&index, "ace guardian"), [(1, 12), (3, 6)]);
+        assert_eq!(search(&index, "ace zzz"), []);
+    }
+
+    #[test]
+    fn search_within_a_field() {
+        let index = index();
+        assert_eq!(search(&index, "flavor:guardian"), [(1, 4)]);
+        assert_eq!(search(&index, "name:guardian"), []);
+        assert_eq!(search(&index, "description:ace"), [(2, 4)]);
+        assert_eq!(search(&index, r#"flavor:"guardian eyes""#), [(1, 8)]);
+    }
+}
@@ -0,0 +1,344 @@
+use egui::RichText;
+use tiger_investment::EntryRef;
+
+use crate::gui::common::{EntryNames, open_entry};
+
+/// Name of the collection the star button adds to.
+const STARRED: &str = "Starred";
+
+/// A named list of entries. Entries are kept by kind and hash, so they still resolve after a game update.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Collection {
+    pub name: String,
+    pub entries: Vec<EntryRef>,
+}
+
+impl Collection {
+    fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn contains(&self, entry: EntryRef) -> bool {
+        self.entries.contains(&entry)
+    }
+
+    /// Adds `entry`, or removes it if it was already in the collection.
+    pub fn toggle(&mut self, entry: EntryRef) {
+        if self.contains(entry) {
+            self.entries.retain(|e| *e != entry);
+        } else {
+            self.entries.push(entry);
+        }
+    }
+}
+
+/// Starred entries and named collections of them. Saved between sessions, and shared as JSON.
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct Bookmarks {
+    /// The starred collection comes first
+    collections: Vec<Collection>,
+    /// Whether the bookmarks sidebar is shown
+    pub visible: bool,
+    #[serde(skip)]
+    new_collection_name: String,
+    /// JSON pasted into the import box
+    #[serde(skip)]
+    import_text: String,
+    /// Outcome of the last import or export
+    #[serde(skip)]
+    status: Option<String>,
+}
+
+impl Default for Bookmarks {
+    fn default() -> Self {
+        Self {
+            collections: vec![Collection::new(STARRED)],
+            visible: false,
+            new_collection_name: String::new(),
+            import_text: String::new(),
+            status: None,
+        }
+    }
+}
+
+impl Bookmarks {
+    fn starred(&mut self) -> &mut Collection {
+        if self.collections.first().is_none_or(|c| c.name != STARRED) {
+            self.collections.insert(0, Collection::new(STARRED));
+        }
+        &mut self.collections[0]
+    }
+
+    pub fn is_starred(&self, entry: EntryRef) -> bool {
+        self.collections
+            .first()
+            .is_some_and(|c| c.name == STARRED && c.contains(entry))
+    }
+
+    /// Draws a star that toggles whether `entry` is starred.
+    /// Right clicking it picks the collections the entry is in.
+    pub fn star_button(&mut self, ui: &mut egui::Ui, entry: EntryRef) {
+        let starred = self.is_starred(entry);
+        let response = ui
+            .button(if starred { "★" } else { "☆" })
+            .on_hover_text("Star, right click to add to a collection");
+        if response.clicked() {
+            self.starred().toggle(entry);
+        }
+        response.context_menu(|ui| {
+            for collection in &mut self.collections {
+                let mut contains = collection.contains(entry);
+                if ui.checkbox(&mut contains, &collection.name).changed() {
+                    collection.toggle(entry);
+                }
+            }
+        });
+    }
+
+    /// Serializes `collections` as pretty printed JSON.
+    pub fn export(collections: &[Collection]) -> String {
+        serde_json::to_string_pretty(collections).unwrap_or_default()
+    }
+
+    /// Reads collections exported with `export`.
+    pub fn parse(json: &str) -> serde_json::Result<Vec<Collection>> {
+        serde_json::from_str(json)
+    }
+
+    /// Adds the entries of `imported` to the collections with the same name, creating any that
+    /// don't exist yet. Returns how many entries were added.
+    pub fn merge(&mut self, imported: Vec<Collection>) -> usize {
+        let mut added = 0;
+        for collection in imported {
+            let index = match self
+                .collections
+                .iter()
+                .position(|c| c.name == collection.name)
+            {
+                Some(index) => index,
+                None => {
+                    self.collections.push(Collection::new(collection.name));
+                    self.collections.len() - 1
+                }
+            };
+            let existing = &mut self.collections[index];
+            for entry in collection.entries {
+                if !existing.contains(entry) {
+                    existing.entries.push(entry);
+                    added += 1;
+                }
+            }
+        }
+        added
+    }
+
+    /// Merges the collections in `json` into these. Returns how many entries were added.
+    pub fn import(&mut self, json: &str) -> serde_json::Result<usize> {
+        Ok(self.merge(Self::parse(json)?))
+    }
+
+    /// Draws the bookmarks sidebar. Clicking an entry opens it.
+    pub fn show(&mut self, ui: &mut egui::Ui, names: &mut EntryNames) {
+        ui.heading("Bookmarks");
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::TextEdit::singleline(&mut self.new_collection_name)
+                    .hint_text("New collection")
+                    .desired_width(140.0),
+            );
+            let name = self.new_collection_name.trim();
+            let is_new = !name.is_empty() && !self.collections.iter().any(|c| c.name == name);
+            if ui.add_enabled(is_new, egui::Button::new("Add")).clicked() {
+                self.collections.push(Collection::new(name));
+                self.new_collection_name.clear();
+            }
+        });
+        ui.separator();
+
+        let mut to_remove = None;
+        egui::ScrollArea::vertical()
+            .max_height((ui.available_height() - 160.0).max(80.0))
+            .show(ui, |ui| {
+                for (i, collection) in self.collections.iter_mut().enumerate() {
+                    egui::CollapsingHeader::new(format!(
+                        "{} ({})",
+                        collection.name,
+                        collection.entries.len()
+                    ))
+                    .id_salt(("collection", i))
+                    .default_open(i == 0)
+                    .show(ui, |ui| {
+                        ui.horizontal(|ui| {
+                            if ui
+                                .small_button("Export")
+                                .on_hover_text("Copy as JSON")
+                                .clicked()
+                            {
+                                ui.ctx()
+                                    .copy_text(Self::export(std::slice::from_ref(collection)));
+                                self.status = Some(format!(
+                                    "Copied \"{}\" to the clipboard",
+                                    collection.name
+                                ));
+                            }
+                            if i > 0 && ui.small_button("Delete").clicked() {
+                                to_remove = Some(i);
+                            }
+                        });
+
+                        let mut removed_entry = None;
+                        for entry in &collection.entries {
+                            ui.horizontal(|ui| {
+                                if ui
+                                    .small_button("\u{E000}")
+                                    .on_hover_text("Remove")
+                                    .clicked()
+                                {
+                                    removed_entry = Some(*entry);
+                                }
+                                match names.get(*entry) {
+                                    Some(name) => {
+                                        if ui
+                                            .link(name)
+                                            .on_hover_text(format!("{} {}", entry.kind, entry.hash))
+                                            .clicked()
+                                            && let Some(data) = entry.resolve()
+                                        {
+                                            open_entry(ui, data);
+                                        }
+                                    }
+                                    None => {
+                                        ui.label(
+                                            RichText::new(format!(
+                                                "{} {} (not found)",
+                                                entry.kind, entry.hash
+                                            ))
+                                            .weak(),
+                                        );
+                                    }
+                                }
+                            });
+                        }
+                        if let Some(entry) = removed_entry {
+                            collection.toggle(entry);
+                        }
+                    });
+                }
+            });
+        if let Some(i) = to_remove {
+            self.collections.remove(i);
+        }
+
+        ui.separator();
+        if ui
+            .button("Export all")
+            .on_hover_text("Copy as JSON")
+            .clicked()
+        {
+            ui.ctx().copy_text(Self::export(&self.collections));
+            self.status = Some("Copied every collection to the clipboard".to_string());
+        }
+        ui.collapsing("Import", |ui| {
+            ui.add(
+                egui::TextEdit::multiline(&mut self.import_text)
+                    .hint_text("Paste exported JSON here")
+                    .desired_rows(4),
+            );
+            if ui
+                .add_enabled(!self.import_text.is_empty(), egui::Button::new("Import"))
+                .clicked()
+            {
+                self.status = Some(match self.import(&self.import_text.clone()) {
+                    Ok(added) => {
+                        self.import_text.clear();
+                        // Look the imported entries up afresh
+                        names.clear();
+                        format!("Imported {added} entries")
+                    }
+                    Err(e) => format!("Failed to import: {e}"),
+                });
+            }
+        });
+        if let Some(status) = &self.status {
+            ui.weak(status);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tiger_investment::InvestmentKind;
+
+    use super::*;
+
+    fn entry(hash: u32) -> EntryRef {
+        EntryRef {
+            kind: InvestmentKind::InventoryItem,
+            hash,
+        }
+    }
+
+    fn collection(name: &str, hashes: &[u32]) -> Collection {
+        Collection {
+            name: name.to_string(),
+            entries: hashes.iter().map(|h| entry(*h)).collect(),
+        }
+    }
+
+    #[test]
+    fn parse_exported_collections() {
+        let collections = vec![collection(STARRED, &[1, 2]), collection("Raid", &[3])];
+        let json = Bookmarks::export(&collections);
+        assert_eq!(Bookmarks::parse(&json).unwrap(), collections);
+
+        assert_eq!(
+            Bookmarks::parse(r#"[{"name":"Raid","entries":[{"kind":"InventoryItem","hash":3}]}]"#)
+                .unwrap(),
+            [collection("Raid", &[3])]
+        );
+    }
+
+    #[test]
+    fn parse_rejects_other_text() {
+        assert!(Bookmarks::parse("").is_err());
+        assert!(Bookmarks::parse("Ace of Spades").is_err());
+        assert!(Bookmarks::parse(r#"{"name":"Raid","entries":[]}"#).is_err());
+        assert!(Bookmarks::parse(r#"[{"name":"Raid"}]"#).is_err());
+    }
+
+    #[test]
+    fn merge_into_collections_with_the_same_name() {
+        let mut bookmarks = Bookmarks::default();
+        bookmarks.starred().toggle(entry(1));
+
+        let added = bookmarks.merge(vec![collection(STARRED, &[1, 2])]);
+        assert_eq!(added, 1);
+        assert_eq!(bookmarks.collections, [collection(STARRED, &[1, 2])]);
+    }
+
+    #[test]
+    fn merge_adds_new_collections() {
+        let mut bookmarks = Bookmarks::default();
+        let added = bookmarks.merge(vec![
+            collection("Raid", &[3, 3, 4]),
+            collection("Raid", &[4, 5]),
+        ]);
+        assert_eq!(added, 3);
+        assert_eq!(
+            bookmarks.collections,
+            [collection(STARRED, &[]), collection("Raid", &[3, 4, 5])]
+        );
+    }
+
+    #[test]
+    fn failed_import_changes_nothing() {
+        let mut bookmarks = Bookmarks::default();
+        assert!(bookmarks.import("[{").is_err());
+        assert_eq!(bookmarks.collections, [collection(STARRED, &[])]);
+        assert!(!bookmarks.is_starred(entry(1)));
+    }
+}
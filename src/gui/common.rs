@@ -7,17 +7,30 @@ use std::{
 use egui::{Color32, RichText};
 use image::ImageFormat;
 use log::error;
+use rustc_hash::FxHashMap;
+use strum::IntoEnumIterator;
 use tiger_investment::{
-    InvestmentData,
+    EntryRef, InvestmentData,
     data::{
         activity::Activity,
-        image::{ColorblindMode, IconContainerType},
-        item::InventoryItem,
+        category::WEAPON_CATEGORY_HASH,
+        destination::{Destination, Place},
+        image::IconContainerType,
+        item::{InventoryItem, roll::WeaponRolls},
+        progression::Progression,
+        season::{Season, SeasonPass, SeasonPassTrack},
+        stat::DisplayedStat,
+        unlock::{Unlock, UnlockUsage},
     },
+    global_instance::investment_manager,
+    manager::Reference,
 };
 use tiger_pkg::TagHash;
 
-use crate::gui::texture::{Texture, cache::TextureCache, icon_container};
+use crate::gui::texture::{
+    Texture, cache::TextureCache, icon_background_color, icon_container, icon_layers,
+    investment_icon,
+};
 
 lazy_static::lazy_static! {
     static ref CF_PNG: NonZeroU32 = clipboard_win::register_format("PNG").unwrap();
@@ -28,14 +41,128 @@ pub trait DisplayUi {
     fn show(&self, texture_cache: TextureCache, hash: u32, ui: &mut egui::Ui);
 }
 
+const OPEN_REQUESTS_ID: &str = "open_entry_requests";
+
+/// Requests `entry` to be opened as a new selection, picked up by the app after the current frame.
+pub fn open_entry(ui: &egui::Ui, entry: InvestmentData) {
+    ui.ctx().data_mut(|d| {
+        d.get_temp_mut_or_default::<Vec<InvestmentData>>(egui::Id::new(OPEN_REQUESTS_ID))
+            .push(entry)
+    });
+}
+
+pub fn take_open_requests(ctx: &egui::Context) -> Vec<InvestmentData> {
+    ctx.data_mut(|d| d.remove_temp::<Vec<InvestmentData>>(egui::Id::new(OPEN_REQUESTS_ID)))
+        .unwrap_or_default()
+}
+
+/// Names of entries that are listed by reference, so they aren't resolved again every frame.
+/// Has to be cleared when the language changes.
+#[derive(Default)]
+pub struct EntryNames {
+    names: FxHashMap<EntryRef, Option<String>>,
+}
+
+impl EntryNames {
+    /// Returns the name of `entry`, or its hash if it has none. `None` if the entry can't be found.
+    pub fn get(&mut self, entry: EntryRef) -> Option<&str> {
+        self.names
+            .entry(entry)
+            .or_insert_with(|| {
+                entry.resolve().map(|data| {
+                    let name = data.name();
+                    if name.is_empty() {
+                        entry.hash.to_string()
+                    } else {
+                        name
+                    }
+                })
+            })
+            .as_deref()
+    }
+
+    pub fn clear(&mut self) {
+        self.names.clear();
+    }
+}
+
 impl DisplayUi for InvestmentData {
     fn show(&self, texture_cache: TextureCache, hash: u32, ui: &mut egui::Ui) {
         match self {
             Self::Activity(act) => act.show(texture_cache, hash, ui),
             Self::InventoryItem(i) => i.show(texture_cache, hash, ui),
+            Self::Progression(p) => p.show(texture_cache, hash, ui),
+            Self::Season(s) => s.show(texture_cache, hash, ui),
+            Self::Destination(d) => d.show(texture_cache, hash, ui),
+            Self::Place(p) => p.show(texture_cache, hash, ui),
             _ => todo!(),
         }
+        referenced_by_ui(self, hash, ui);
+    }
+}
+
+/// Lists every entry pointing to `entry`, and every entry sharing one of the plug sets, icons,
+/// strings or other targets it points to.
+fn referenced_by_ui(entry: &InvestmentData, hash: u32, ui: &mut egui::Ui) {
+    ui.collapsing(RichText::new("Referenced By").size(15.0), |ui| {
+        let im = investment_manager();
+        let references = im.references();
+        let this = entry.entry_ref();
+
+        let mut targets = Vec::new();
+        if let Some(target) = im.reference_target(entry) {
+            targets.push(("This entry".to_string(), target));
+        }
+        for (target, field) in references.shared_targets(this) {
+            targets.push((format!("Same {field}"), *target));
+        }
+
+        for (label, target) in targets {
+            let refs: Vec<Reference> = references
+                .referenced_by(target)
+                .iter()
+                .filter(|r| r.from != this)
+                .copied()
+                .collect();
+            egui::CollapsingHeader::new(format!("{label} ({target}): {}", refs.len()))
+                .id_salt(("referenced_by", hash, target))
+                .default_open(label == "This entry")
+                .show(ui, |ui| reference_list(ui, &refs));
+        }
+    });
+}
+
+fn reference_list(ui: &mut egui::Ui, refs: &[Reference]) {
+    if refs.is_empty() {
+        ui.label("None");
+        return;
     }
+    let row_height = ui.text_style_height(&egui::TextStyle::Body);
+    egui::ScrollArea::vertical()
+        .max_height(240.0)
+        .auto_shrink([false, true])
+        .show_rows(ui, row_height, refs.len(), |ui, rows| {
+            for reference in &refs[rows] {
+                let Some(entry) = reference.from.resolve() else {
+                    ui.label(format!(
+                        "{} {} ({})",
+                        reference.from.kind, reference.from.hash, reference.field
+                    ));
+                    continue;
+                };
+                if ui
+                    .link(format!(
+                        "{}: {} ({})",
+                        reference.from.kind,
+                        entry.name(),
+                        reference.field
+                    ))
+                    .clicked()
+                {
+                    open_entry(ui, entry);
+                }
+            }
+        });
 }
 
 impl DisplayUi for Activity {
@@ -79,6 +206,31 @@ impl DisplayUi for Activity {
                 ));
             },
         );
+        let destination = self.destination();
+        let place = self.place();
+        if destination.is_some() || place.is_some() {
+            ui.collapsing(RichText::new("Destination").size(15.0), |ui| {
+                if let Some(destination) = destination {
+                    ui.horizontal(|ui| {
+                        ui.label("destination:");
+                        if ui
+                            .link(destination.data.name.get().unwrap_or_default())
+                            .clicked()
+                        {
+                            open_entry(ui, InvestmentData::Destination(Box::new(destination)));
+                        }
+                    });
+                }
+                if let Some(place) = place {
+                    ui.horizontal(|ui| {
+                        ui.label("place:");
+                        if ui.link(place.data.name.get().unwrap_or_default()).clicked() {
+                            open_entry(ui, InvestmentData::Place(Box::new(place)));
+                        }
+                    });
+                }
+            });
+        }
         ui.collapsing(RichText::new("Modifiers").size(15.0), |ui| {
             for (i, modifier) in self.display.modifiers.iter().enumerate() {
                 ui.collapsing(format!("Modifier {i}"), |ui| {
@@ -98,10 +250,40 @@ impl DisplayUi for Activity {
             for (i, phase) in self.data.insertion_points.0.phases.iter().enumerate() {
                 ui.collapsing(format!("Insertion Point {i}"), |ui| {
                     ui.label(format!("phase: {}", phase.phase_hash));
-                    ui.label(format!("unlock_index: {}", phase.unlock_index));
+                    unlock_ui(
+                        ui,
+                        ("insertion_point", hash, i),
+                        "unlock",
+                        phase.unlock(),
+                        phase.unlock_index as i64,
+                    );
                 });
             }
         });
+        let requirements = self.requirements();
+        if !requirements.is_empty() {
+            ui.collapsing(RichText::new("Requirements").size(15.0), |ui| {
+                for (i, requirement) in requirements.iter().enumerate() {
+                    unlock_ui(
+                        ui,
+                        ("requirement", hash, i),
+                        "unlock",
+                        requirement.unlock(),
+                        requirement.unlock_index as i64,
+                    );
+                    ui.indent(("requirement_strings", i), |ui| {
+                        ui.label(format!(
+                            "unk4: \"{}\"",
+                            requirement.unk4.get().unwrap_or_default()
+                        ));
+                        ui.label(format!(
+                            "unk8: \"{}\"",
+                            requirement.unk8.get().unwrap_or_default()
+                        ));
+                    });
+                }
+            });
+        }
     }
 }
 
@@ -112,29 +294,10 @@ impl DisplayUi for InventoryItem {
                 return;
             };
 
-            let mut textures = Vec::new();
-            textures.append(
-                &mut icon
-                    .get_background_textures(Some(ColorblindMode::None))
-                    .iter()
-                    .map(|x| (*x, IconContainerType::Background))
-                    .collect::<Vec<_>>(),
-            );
-            textures.append(
-                &mut icon
-                    .get_primary_textures()
-                    .iter()
-                    .map(|x| (*x, IconContainerType::Primary))
-                    .collect::<Vec<_>>(),
-            );
+            let mut textures = icon_layers(&icon);
             icon_container(
                 ui,
-                Color32::from_rgba_unmultiplied(
-                    (icon.data.background_color[0] * 255.0) as u8,
-                    (icon.data.background_color[1] * 255.0) as u8,
-                    (icon.data.background_color[2] * 255.0) as u8,
-                    (icon.data.background_color[3] * 255.0) as u8,
-                ),
+                icon_background_color(&icon),
                 textures.clone(),
                 texture_cache.clone(),
                 hash,
@@ -171,7 +334,207 @@ impl DisplayUi for InventoryItem {
                 "flavor: \"{}\"",
                 self.display.flavor.get().unwrap_or_default()
             ));
+            if let Some(tier) = self.tier() {
+                ui.label(format!("tier: {tier}"));
+            }
+            if let Some(class) = self.restriction.class {
+                let class_name = investment_manager()
+                    .characters()
+                    .class(class)
+                    .and_then(|c| c.name.get())
+                    .unwrap_or_else(|| class.to_string());
+                ui.label(format!("class: \"{class_name}\""));
+            }
+            let gendered_strings = self.gendered_strings();
+            if !gendered_strings.is_empty() {
+                ui.collapsing("Gendered Variants", |ui| {
+                    for strings in gendered_strings {
+                        let gender = strings
+                            .gender
+                            .map(|g| g.to_string())
+                            .unwrap_or_else(|| "Unknown".to_string());
+                        ui.collapsing(gender, |ui| {
+                            ui.label(format!("name: \"{}\"", strings.name.unwrap_or_default()));
+                            ui.label(format!(
+                                "type: \"{}\"",
+                                strings.item_type.unwrap_or_default()
+                            ));
+                        });
+                    }
+                });
+            }
         });
+        let categories = self.categories();
+        if !categories.is_empty() {
+            ui.collapsing(RichText::new("Categories").size(15.0), |ui| {
+                for category in categories {
+                    ui.label(category.display_path())
+                        .on_hover_text(format!("hash: {}", category.data.hash));
+                }
+            });
+        }
+        let item_sets = self.item_sets();
+        if !item_sets.is_empty() {
+            ui.collapsing(RichText::new("Item Sets").size(15.0), |ui| {
+                for (i, set) in item_sets.iter().enumerate() {
+                    let title = match &set.name {
+                        Some(name) => format!("{}: {name}", set.kind),
+                        None => set.kind.to_string(),
+                    };
+                    egui::CollapsingHeader::new(title)
+                        .id_salt(("item_set", hash, i))
+                        .default_open(true)
+                        .show(ui, |ui| {
+                            ui.horizontal_wrapped(|ui| {
+                                for item in set.items() {
+                                    let item_name = item.display.name.get().unwrap_or_default();
+                                    let response = investment_icon(
+                                        ui,
+                                        item.display.icon_index,
+                                        48.0,
+                                        texture_cache.clone(),
+                                        item.data.hash,
+                                    );
+                                    if item.data.hash == self.data.hash {
+                                        ui.painter().rect_stroke(
+                                            response.rect,
+                                            0.0,
+                                            egui::Stroke::new(2.0, Color32::YELLOW),
+                                            egui::StrokeKind::Outside,
+                                        );
+                                    }
+                                    if response.on_hover_text(item_name).clicked()
+                                        && item.data.hash != self.data.hash
+                                    {
+                                        open_entry(
+                                            ui,
+                                            InvestmentData::InventoryItem(Box::new(item)),
+                                        );
+                                    }
+                                }
+                            });
+                            for perk in &set.perks {
+                                ui.label(format!(
+                                    "{} pieces: sandbox_perk_index {}",
+                                    perk.required_count, perk.sandbox_perk_index
+                                ));
+                            }
+                        });
+                }
+            });
+        }
+        if let Some(quest_line) = self.quest_line() {
+            ui.collapsing(RichText::new("Quest Line").size(15.0), |ui| {
+                if let Some(parent) = &quest_line.quest_line
+                    && ui
+                        .link(parent.display.name.get().unwrap_or_default())
+                        .clicked()
+                {
+                    open_entry(ui, InvestmentData::InventoryItem(Box::new(parent.clone())));
+                }
+                for (i, step) in quest_line.steps.iter().enumerate() {
+                    let step_name = format!(
+                        "Step {}: {}",
+                        i + 1,
+                        step.item.display.name.get().unwrap_or_default()
+                    );
+                    if i == quest_line.current {
+                        ui.label(RichText::new(step_name).strong());
+                    } else if ui.link(step_name).clicked() {
+                        open_entry(
+                            ui,
+                            InvestmentData::InventoryItem(Box::new(step.item.clone())),
+                        );
+                    }
+                    ui.indent(("quest_step", i), |ui| {
+                        for objective in &step.objectives {
+                            ui.label(format!(
+                                "0/{} {}",
+                                objective.completion_value,
+                                objective.progress_description.get().unwrap_or_default()
+                            ));
+                        }
+                    });
+                }
+            });
+        }
+        let objectives = self.objectives();
+        if !objectives.is_empty() {
+            ui.collapsing(RichText::new("Objectives").size(15.0), |ui| {
+                for (i, objective) in objectives.iter().enumerate() {
+                    ui.label(format!(
+                        "0/{} {}",
+                        objective.completion_value,
+                        objective.progress_description.get().unwrap_or_default()
+                    ));
+                    ui.indent(("objective", i), |ui| {
+                        unlock_ui(
+                            ui,
+                            ("objective_unlock", hash, i),
+                            "unlock_value",
+                            objective.unlock_value(),
+                            objective.unlock_value_index as i64,
+                        );
+                    });
+                }
+            });
+        }
+        if let Some(item_index) = investment_manager().items().index_of_hash(self.data.hash) {
+            let seasons = investment_manager().seasons();
+            let season_rewards = seasons.rewards_for_item(item_index);
+            if !season_rewards.is_empty() {
+                ui.collapsing(RichText::new("Season Pass").size(15.0), |ui| {
+                    for (season, reward) in season_rewards {
+                        ui.horizontal(|ui| {
+                            if ui
+                                .link(season.data.name.get().unwrap_or_default())
+                                .clicked()
+                            {
+                                open_entry(ui, InvestmentData::Season(Box::new(season.clone())));
+                            }
+                            ui.label(format!("rank {} ({})", reward.rank, reward.track));
+                        });
+                    }
+                });
+            }
+        }
+        if self.data.sockets.0.is_some() && self.in_category(WEAPON_CATEGORY_HASH) {
+            ui.collapsing(RichText::new("Weapon Rolls").size(15.0), |ui| {
+                match self.weapon_rolls() {
+                    Some(rolls) => weapon_roll_picker(ui, hash, &rolls, texture_cache.clone()),
+                    None => {
+                        ui.label("No perk columns");
+                    }
+                }
+            });
+        }
+        if self.data.sockets.0.is_some() {
+            ui.collapsing(RichText::new("Ornaments").size(15.0), |ui| {
+                item_gallery(
+                    ui,
+                    ("ornaments", hash),
+                    self.ornaments(),
+                    texture_cache.clone(),
+                );
+            });
+            ui.collapsing(RichText::new("Shaders").size(15.0), |ui| {
+                item_gallery(ui, ("shaders", hash), self.shaders(), texture_cache.clone());
+            });
+        }
+        if let Some(kind) = self.cosmetic_kind() {
+            ui.collapsing(RichText::new("Applies To").size(15.0), |ui| {
+                ui.label(format!(
+                    "{kind}: \"{}\"",
+                    self.plug_category_identifier().unwrap_or_default()
+                ));
+                item_gallery(
+                    ui,
+                    ("applies_to", hash),
+                    self.applies_to(),
+                    texture_cache.clone(),
+                );
+            });
+        }
         if let Some(sockets) = &self.data.sockets.0 {
             ui.collapsing(RichText::new("Sockets").size(15.0), |ui| {
                 for (i, socket) in sockets.data.iter().enumerate() {
@@ -186,8 +549,8 @@ impl DisplayUi for InventoryItem {
                             socket.reusable_plugset_index
                         ));
                         ui.label(format!(
-                            "reusable_plugset_index_2: {}",
-                            socket.reusable_plugset_index_2
+                            "randomized_plugset_index: {}",
+                            socket.randomized_plugset_index
                         ));
                         egui::CollapsingHeader::new("plug_items")
                             .id_salt(socket.socket_index + socket.reusable_plugset_index)
@@ -202,12 +565,50 @@ impl DisplayUi for InventoryItem {
         }
         if let Some(stats_perks) = &self.data.stats_perks.0 {
             ui.collapsing(RichText::new("Stats").size(15.0), |ui| {
-                for stat in &stats_perks.stats {
-                    ui.collapsing(format!("Stat Type {}", stat.stat_type), |ui| {
-                        ui.label(format!("type: {}", stat.stat_type));
-                        ui.label(format!("value: {}", stat.stat_value));
+                ui.label(format!(
+                    "stat_group_index: {}",
+                    stats_perks.stat_group_index
+                ));
+                let roll: Option<Vec<usize>> = ui
+                    .data(|d| d.get_temp::<Vec<Option<usize>>>(roll_selection_id(hash)))
+                    .map(|r| r.into_iter().flatten().collect());
+                let base = self.displayed_stats(&[]);
+                let rolled = roll.map(|r| self.displayed_stats(&r));
+
+                egui::Grid::new(("item_stats", hash))
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.label(RichText::new("Stat Type").strong());
+                        ui.label(RichText::new("Investment").strong());
+                        ui.label(RichText::new("Displayed").strong());
+                        if rolled.is_some() {
+                            ui.label(RichText::new("Selected Roll").strong());
+                        }
+                        ui.end_row();
+
+                        for (i, stat) in base.iter().enumerate() {
+                            ui.label(stat.stat_type.to_string());
+                            ui.label(stat.investment_value.to_string());
+                            stat_value(ui, stat);
+                            if let Some(rolled_stat) = rolled.as_ref().and_then(|r| r.get(i)) {
+                                let delta = rolled_stat.display_value - stat.display_value;
+                                ui.horizontal(|ui| {
+                                    stat_value(ui, rolled_stat);
+                                    if delta != 0 {
+                                        ui.colored_label(
+                                            if delta > 0 {
+                                                Color32::GREEN
+                                            } else {
+                                                Color32::RED
+                                            },
+                                            format!("{delta:+}"),
+                                        );
+                                    }
+                                });
+                            }
+                            ui.end_row();
+                        }
                     });
-                }
             });
 
             ui.collapsing(RichText::new("Perks").size(15.0), |ui| {
@@ -219,6 +620,445 @@ impl DisplayUi for InventoryItem {
     }
 }
 
+impl DisplayUi for Progression {
+    fn show(&self, texture_cache: TextureCache, hash: u32, ui: &mut egui::Ui) {
+        ui.collapsing(RichText::new("Display Properties").size(15.0), |ui| {
+            investment_icon(
+                ui,
+                self.display.icon_index,
+                96.0,
+                texture_cache.clone(),
+                hash,
+            );
+            ui.label(format!(
+                "name: \"{}\"",
+                self.display.name.get().unwrap_or_default()
+            ));
+            ui.label(format!(
+                "description: \"{}\"",
+                self.display.description.get().unwrap_or_default()
+            ));
+            ui.label(format!("scope: {}", self.data.scope));
+            ui.label(format!(
+                "repeat_last_step: {}",
+                self.data.repeat_last_step != 0
+            ));
+        });
+        ui.collapsing(RichText::new("Steps").size(15.0), |ui| {
+            egui::Grid::new(("progression_steps", hash))
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.label(RichText::new("Rank").strong());
+                    ui.label("");
+                    ui.label(RichText::new("Name").strong());
+                    ui.label(RichText::new("Progress").strong());
+                    ui.label(RichText::new("Total").strong());
+                    ui.label(RichText::new("Rewards").strong());
+                    ui.end_row();
+
+                    for (i, step) in self.steps().iter().enumerate() {
+                        ui.label((i + 1).to_string());
+                        investment_icon(ui, step.icon_index, 32.0, texture_cache.clone(), hash);
+                        ui.label(step.name.clone().unwrap_or_default());
+                        ui.label(step.progress_total.to_string());
+                        ui.label(step.cumulative_total.to_string())
+                            .on_hover_text("Total progress needed to reach this rank");
+                        ui.horizontal(|ui| {
+                            for reward in &step.rewards {
+                                let reward_name =
+                                    reward.item.display.name.get().unwrap_or_default();
+                                let response = investment_icon(
+                                    ui,
+                                    reward.item.display.icon_index,
+                                    32.0,
+                                    texture_cache.clone(),
+                                    reward.item.data.hash,
+                                )
+                                .on_hover_text(format!("{reward_name} x{}", reward.quantity));
+                                if response.clicked() {
+                                    open_entry(
+                                        ui,
+                                        InvestmentData::InventoryItem(Box::new(
+                                            reward.item.clone(),
+                                        )),
+                                    );
+                                }
+                            }
+                        });
+                        ui.end_row();
+                    }
+                });
+        });
+    }
+}
+
+impl DisplayUi for Season {
+    fn show(&self, texture_cache: TextureCache, hash: u32, ui: &mut egui::Ui) {
+        ui.collapsing(RichText::new("Display Properties").size(15.0), |ui| {
+            investment_icon(ui, self.data.icon_index, 96.0, texture_cache.clone(), hash);
+            ui.label(format!(
+                "name: \"{}\"",
+                self.data.name.get().unwrap_or_default()
+            ));
+            ui.label(format!(
+                "description: \"{}\"",
+                self.data.description.get().unwrap_or_default()
+            ));
+            ui.label(format!("season_number: {}", self.data.season_number));
+            ui.label(format!("start_marker: {}", self.data.start_marker));
+            ui.label(format!("end_marker: {}", self.data.end_marker));
+            ui.label(format!("seal: {}", self.data.seal_presentation_node_hash));
+            if let Some(artifact) = self.artifact() {
+                ui.horizontal(|ui| {
+                    ui.label("artifact:");
+                    if ui
+                        .link(artifact.display.name.get().unwrap_or_default())
+                        .clicked()
+                    {
+                        open_entry(
+                            ui,
+                            InvestmentData::InventoryItem(Box::new(artifact.clone())),
+                        );
+                    }
+                });
+            }
+        });
+        for pass in self.season_passes() {
+            ui.collapsing(
+                RichText::new(format!(
+                    "Season Pass: {}",
+                    pass.data.name.get().unwrap_or_default()
+                ))
+                .size(15.0),
+                |ui| season_pass_grid(ui, &pass, texture_cache.clone()),
+            );
+        }
+    }
+}
+
+/// Shows a resolved unlock, which can be expanded to list all content gated by it.
+/// Falls back to the raw index when the unlock can't be resolved.
+fn unlock_ui(
+    ui: &mut egui::Ui,
+    id_salt: impl std::hash::Hash,
+    label: &str,
+    unlock: Option<Unlock>,
+    raw_index: i64,
+) {
+    let Some(unlock) = unlock else {
+        ui.label(format!("{label}: {raw_index}"));
+        return;
+    };
+
+    egui::CollapsingHeader::new(format!("{label}: {unlock}"))
+        .id_salt(id_salt)
+        .show(ui, |ui| {
+            let usages = investment_manager()
+                .unlocks()
+                .usages(unlock.kind, unlock.index);
+            ui.label(format!("Used by {} entries", usages.len()));
+            for usage in usages {
+                unlock_usage_ui(ui, &usage);
+            }
+        });
+}
+
+fn unlock_usage_ui(ui: &mut egui::Ui, usage: &UnlockUsage) {
+    let entry = match usage {
+        UnlockUsage::InsertionPoint { activity_index, .. }
+        | UnlockUsage::ActivityRequirement { activity_index } => u16::try_from(*activity_index)
+            .ok()
+            .and_then(|i| investment_manager().activities().get_activity_by_index(i)),
+        UnlockUsage::Item { item_index } => investment_manager()
+            .items()
+            .get_item_by_index(*item_index)
+            .map(|i| InvestmentData::InventoryItem(Box::new(i))),
+        UnlockUsage::Objective { objective_index } => {
+            if let Some(objective) = investment_manager()
+                .objectives()
+                .get_by_index(*objective_index)
+            {
+                ui.label(format!(
+                    "Objective: \"{}\"",
+                    objective.progress_description.get().unwrap_or_default()
+                ));
+            }
+            return;
+        }
+    };
+    let Some(entry) = entry else {
+        return;
+    };
+
+    let kind: &'static str = (&entry).into();
+    let text = match usage {
+        UnlockUsage::InsertionPoint { phase_hash, .. } => {
+            format!("{kind}: {} (phase {phase_hash})", entry.name())
+        }
+        UnlockUsage::ActivityRequirement { .. } => {
+            format!("{kind}: {} (requirement)", entry.name())
+        }
+        _ => format!("{kind}: {}", entry.name()),
+    };
+    if ui.link(text).clicked() {
+        open_entry(ui, entry);
+    }
+}
+
+/// Draws a displayed stat as a number, or as a bar like in game.
+fn stat_value(ui: &mut egui::Ui, stat: &DisplayedStat) {
+    if stat.display_as_numeric || stat.maximum_value <= 0 {
+        ui.label(stat.display_value.to_string());
+    } else {
+        ui.add(
+            egui::ProgressBar::new(stat.display_value as f32 / stat.maximum_value as f32)
+                .desired_width(120.0)
+                .text(stat.display_value.to_string()),
+        );
+    }
+}
+
+fn roll_selection_id(hash: u32) -> egui::Id {
+    egui::Id::new(("weapon_roll_selection", hash))
+}
+
+/// Lays out the perk columns of a weapon side by side, with one perk picked per column.
+fn weapon_roll_picker(
+    ui: &mut egui::Ui,
+    hash: u32,
+    rolls: &WeaponRolls,
+    texture_cache: TextureCache,
+) {
+    let id = roll_selection_id(hash);
+    let mut selected: Vec<Option<usize>> = ui.data_mut(|d| {
+        d.get_temp_mut_or_insert_with(id, || rolls.curated_roll())
+            .clone()
+    });
+
+    ui.horizontal(|ui| {
+        ui.label(format!("combinations: {}", rolls.combination_count()));
+        if ui.button("Curated roll").clicked() {
+            selected = rolls.curated_roll();
+        }
+    });
+
+    egui::ScrollArea::horizontal()
+        .id_salt(("weapon_rolls", hash))
+        .show(ui, |ui| {
+            ui.horizontal_top(|ui| {
+                for (column, pick) in rolls.columns.iter().zip(selected.iter_mut()) {
+                    ui.vertical(|ui| {
+                        ui.label(
+                            RichText::new(if column.randomized { "Random" } else { "Fixed" })
+                                .strong(),
+                        );
+                        if pick.is_none() {
+                            ui.weak("none");
+                        }
+                        for plug in &column.pool {
+                            let response = investment_icon(
+                                ui,
+                                plug.item.display.icon_index,
+                                40.0,
+                                texture_cache.clone(),
+                                plug.item.data.hash,
+                            );
+                            if !plug.can_roll {
+                                ui.painter().rect_filled(
+                                    response.rect,
+                                    0.0,
+                                    Color32::from_black_alpha(160),
+                                );
+                            }
+                            if *pick == Some(plug.item_index) {
+                                ui.painter().rect_stroke(
+                                    response.rect,
+                                    0.0,
+                                    egui::Stroke::new(2.0, Color32::YELLOW),
+                                    egui::StrokeKind::Outside,
+                                );
+                            }
+
+                            let mut hover = plug.item.display.name.get().unwrap_or_default();
+                            if !plug.can_roll {
+                                hover.push_str(" (no longer drops)");
+                            }
+                            hover.push_str("\nDouble click to open");
+                            let response = response.on_hover_text(hover);
+                            if response.clicked() {
+                                *pick = Some(plug.item_index);
+                            }
+                            if response.double_clicked() {
+                                open_entry(
+                                    ui,
+                                    InvestmentData::InventoryItem(Box::new(plug.item.clone())),
+                                );
+                            }
+                        }
+                    });
+                }
+            });
+        });
+
+    ui.data_mut(|d| d.insert_temp(id, selected));
+}
+
+/// Draws `items` as a scrollable gallery of icons, opening an item when it's clicked.
+fn item_gallery(
+    ui: &mut egui::Ui,
+    id_salt: impl std::hash::Hash,
+    items: Vec<InventoryItem>,
+    texture_cache: TextureCache,
+) {
+    if items.is_empty() {
+        ui.label("None");
+        return;
+    }
+    ui.label(format!("{} items", items.len()));
+    egui::ScrollArea::vertical()
+        .id_salt(id_salt)
+        .max_height(320.0)
+        .show(ui, |ui| {
+            ui.horizontal_wrapped(|ui| {
+                for item in items {
+                    let item_name = item.display.name.get().unwrap_or_default();
+                    if investment_icon(
+                        ui,
+                        item.display.icon_index,
+                        48.0,
+                        texture_cache.clone(),
+                        item.data.hash,
+                    )
+                    .on_hover_text(item_name)
+                    .clicked()
+                    {
+                        open_entry(ui, InvestmentData::InventoryItem(Box::new(item)));
+                    }
+                }
+            });
+        });
+}
+
+/// Lays out the reward tracks of a season pass as a grid of icons, one column per rank.
+fn season_pass_grid(ui: &mut egui::Ui, pass: &SeasonPass, texture_cache: TextureCache) {
+    let rewards = pass.rewards();
+    let mut ranks: Vec<u16> = rewards.iter().map(|r| r.rank).collect();
+    ranks.dedup();
+
+    egui::ScrollArea::horizontal()
+        .id_salt(("season_pass", pass.data.hash))
+        .show(ui, |ui| {
+            egui::Grid::new(("season_pass_grid", pass.data.hash)).show(ui, |ui| {
+                ui.label(RichText::new("Rank").strong());
+                for rank in &ranks {
+                    ui.label(rank.to_string());
+                }
+                ui.end_row();
+
+                for track in SeasonPassTrack::iter() {
+                    ui.label(RichText::new(track.to_string()).strong());
+                    for rank in &ranks {
+                        ui.vertical(|ui| {
+                            for reward in rewards
+                                .iter()
+                                .filter(|r| r.rank == *rank && r.track == track)
+                            {
+                                let response = investment_icon(
+                                    ui,
+                                    reward.item.display.icon_index,
+                                    48.0,
+                                    texture_cache.clone(),
+                                    reward.item.data.hash,
+                                )
+                                .on_hover_text(format!(
+                                    "{} x{}",
+                                    reward.item.display.name.get().unwrap_or_default(),
+                                    reward.quantity
+                                ));
+                                if response.clicked() {
+                                    open_entry(
+                                        ui,
+                                        InvestmentData::InventoryItem(Box::new(
+                                            reward.item.clone(),
+                                        )),
+                                    );
+                                }
+                            }
+                        });
+                    }
+                    ui.end_row();
+                }
+            });
+        });
+}
+
+impl DisplayUi for Destination {
+    fn show(&self, texture_cache: TextureCache, hash: u32, ui: &mut egui::Ui) {
+        ui.collapsing(RichText::new("Display Properties").size(15.0), |ui| {
+            investment_icon(ui, self.data.icon_index, 96.0, texture_cache.clone(), hash);
+            ui.label(format!(
+                "name: \"{}\"",
+                self.data.name.get().unwrap_or_default()
+            ));
+            ui.label(format!(
+                "description: \"{}\"",
+                self.data.description.get().unwrap_or_default()
+            ));
+            if let Some(place) = self.place() {
+                ui.horizontal(|ui| {
+                    ui.label("place:");
+                    if ui.link(place.data.name.get().unwrap_or_default()).clicked() {
+                        open_entry(ui, InvestmentData::Place(Box::new(place)));
+                    }
+                });
+            }
+        });
+        ui.collapsing(RichText::new("Bubbles").size(15.0), |ui| {
+            for (i, bubble) in self.data.bubbles.iter().enumerate() {
+                ui.collapsing(format!("Bubble {i}"), |ui| {
+                    ui.label(format!("hash: {}", bubble.hash));
+                    ui.label(format!(
+                        "name: \"{}\"",
+                        bubble.name.get().unwrap_or_default()
+                    ));
+                    ui.label(format!(
+                        "description: \"{}\"",
+                        bubble.description.get().unwrap_or_default()
+                    ));
+                });
+            }
+        });
+        if let Some(index) = investment_manager()
+            .destinations()
+            .destination_index_of_hash(self.data.hash)
+        {
+            ui.collapsing(RichText::new("Activities").size(15.0), |ui| {
+                for activity in investment_manager().activities().get_by_destination(index) {
+                    if ui.link(activity.name()).clicked() {
+                        open_entry(ui, activity.clone());
+                    }
+                }
+            });
+        }
+    }
+}
+
+impl DisplayUi for Place {
+    fn show(&self, texture_cache: TextureCache, hash: u32, ui: &mut egui::Ui) {
+        ui.collapsing(RichText::new("Display Properties").size(15.0), |ui| {
+            investment_icon(ui, self.data.icon_index, 96.0, texture_cache.clone(), hash);
+            ui.label(format!(
+                "name: \"{}\"",
+                self.data.name.get().unwrap_or_default()
+            ));
+            ui.label(format!(
+                "description: \"{}\"",
+                self.data.description.get().unwrap_or_default()
+            ));
+        });
+    }
+}
+
 pub trait ResponseExt {
     fn tag_context_with_texture(
         self,
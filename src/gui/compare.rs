@@ -0,0 +1,164 @@
+use egui::{Color32, RichText};
+use tiger_investment::{
+    EntryRef, InvestmentData, InvestmentKind,
+    diff::{FieldDiff, diff_entries},
+};
+
+use crate::gui::common::EntryNames;
+
+/// Side-by-side comparison of two selected entries of the same kind, e.g. the adept and normal
+/// versions of a weapon.
+pub struct CompareWindow {
+    left: EntryRef,
+    right: EntryRef,
+    only_changes: bool,
+    /// Diff of the current pair, redone when either side changes
+    diffs: Option<(EntryRef, EntryRef, Vec<FieldDiff>)>,
+}
+
+impl CompareWindow {
+    /// Compares the two most recently selected entries of the same kind, if there are any.
+    pub fn for_selection(selected: &[InvestmentData]) -> Option<Self> {
+        let (left, right) = selected.iter().enumerate().rev().find_map(|(i, right)| {
+            selected[..i]
+                .iter()
+                .rev()
+                .find(|left| left.kind() == right.kind())
+                .map(|left| (left.entry_ref(), right.entry_ref()))
+        })?;
+        Some(Self {
+            left,
+            right,
+            only_changes: false,
+            diffs: None,
+        })
+    }
+
+    /// Returns false once the window has been closed.
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        selected: &[InvestmentData],
+        names: &mut EntryNames,
+    ) -> bool {
+        let mut open = true;
+        egui::Window::new("Compare")
+            .open(&mut open)
+            .default_size([720.0, 480.0])
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    entry_picker(ui, "compare_left", &mut self.left, selected, None, names);
+                    if ui.button("⇄").on_hover_text("Swap").clicked() {
+                        std::mem::swap(&mut self.left, &mut self.right);
+                    }
+                    let kind = self.left.kind;
+                    entry_picker(
+                        ui,
+                        "compare_right",
+                        &mut self.right,
+                        selected,
+                        Some(kind),
+                        names,
+                    );
+                    ui.checkbox(&mut self.only_changes, "Only differences");
+                });
+                ui.separator();
+
+                let is_stale = !matches!(
+                    &self.diffs,
+                    Some((left, right, _)) if *left == self.left && *right == self.right
+                );
+                if is_stale {
+                    let diffs = self
+                        .left
+                        .resolve()
+                        .zip(self.right.resolve())
+                        .and_then(|(left, right)| diff_entries(&left, &right))
+                        .unwrap_or_default();
+                    self.diffs = Some((self.left, self.right, diffs));
+                }
+                let Some((_, _, diffs)) = &self.diffs else {
+                    return;
+                };
+                if diffs.is_empty() {
+                    ui.label("Pick two entries of the same kind to compare");
+                    return;
+                }
+
+                egui::ScrollArea::both().show(ui, |ui| {
+                    diff_grid(ui, diffs, self.only_changes);
+                });
+            });
+        open
+    }
+}
+
+/// Picks one of the selected entries, only offering entries of `kind` if given.
+fn entry_picker(
+    ui: &mut egui::Ui,
+    id_salt: &str,
+    current: &mut EntryRef,
+    selected: &[InvestmentData],
+    kind: Option<InvestmentKind>,
+    names: &mut EntryNames,
+) {
+    let name = names
+        .get(*current)
+        .map(str::to_string)
+        .unwrap_or_else(|| current.hash.to_string());
+    egui::ComboBox::from_id_salt(id_salt)
+        .selected_text(name)
+        .width(220.0)
+        .show_ui(ui, |ui| {
+            for entry in selected {
+                if kind.is_some_and(|k| k != entry.kind()) {
+                    continue;
+                }
+                ui.selectable_value(
+                    current,
+                    entry.entry_ref(),
+                    format!("{} ({})", entry.name(), entry.hash()),
+                );
+            }
+        });
+}
+
+/// Lays the fields out in a grid, with fields that differ highlighted.
+fn diff_grid(ui: &mut egui::Ui, diffs: &[FieldDiff], only_changes: bool) {
+    let changed = ui.visuals().warn_fg_color;
+    egui::Grid::new("compare_grid")
+        .striped(true)
+        .num_columns(3)
+        .show(ui, |ui| {
+            let mut section = "";
+            for diff in diffs {
+                if only_changes && !diff.is_changed() {
+                    continue;
+                }
+                if diff.section != section {
+                    section = diff.section;
+                    ui.label(RichText::new(section).strong().size(15.0));
+                    ui.end_row();
+                }
+
+                let is_changed = diff.is_changed();
+                ui.label(&diff.field);
+                for value in [&diff.left, &diff.right] {
+                    let text = match value {
+                        Some(value) => RichText::new(value),
+                        None => RichText::new("(missing)").italics(),
+                    };
+                    ui.add(
+                        egui::Label::new(if is_changed {
+                            text.color(changed)
+                                .background_color(Color32::from_black_alpha(48))
+                        } else {
+                            text
+                        })
+                        .wrap(),
+                    );
+                }
+                ui.end_row();
+            }
+        });
+}
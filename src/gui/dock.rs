@@ -0,0 +1,195 @@
+use std::collections::BTreeMap;
+
+use egui::{Id, WidgetText};
+use egui_dock::{DockArea, DockState, Style, TabViewer};
+use rustc_hash::FxHashSet;
+use tiger_investment::{EntryRef, InvestmentData};
+
+use crate::gui::{bookmarks::Bookmarks, common::DisplayUi, texture::cache::TextureCache};
+
+/// A tab showing the details of one selected entry.
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+pub struct DetailTab {
+    entry: EntryRef,
+    /// Looked up when the tab is first drawn
+    #[serde(skip)]
+    data: Option<InvestmentData>,
+}
+
+impl DetailTab {
+    fn data(&mut self) -> Option<&InvestmentData> {
+        if self.data.is_none() {
+            self.data = self.entry.resolve();
+        }
+        self.data.as_ref()
+    }
+}
+
+/// The selected entries, each in a tab that can be split off, moved around and closed.
+/// Saved between sessions, along with any layouts saved by name.
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct DetailDock {
+    state: DockState<DetailTab>,
+    layouts: BTreeMap<String, DockState<DetailTab>>,
+    /// Name typed into the layouts menu
+    #[serde(skip)]
+    new_layout_name: String,
+}
+
+impl Default for DetailDock {
+    fn default() -> Self {
+        Self {
+            state: DockState::new(Vec::new()),
+            layouts: BTreeMap::new(),
+            new_layout_name: String::new(),
+        }
+    }
+}
+
+impl DetailDock {
+    pub fn is_empty(&self) -> bool {
+        self.state.iter_all_tabs().next().is_none()
+    }
+
+    /// Entries with an open tab.
+    pub fn entries(&self) -> Vec<EntryRef> {
+        self.state
+            .iter_all_tabs()
+            .map(|(_, tab)| tab.entry)
+            .collect()
+    }
+
+    /// Closes the tabs of entries that are no longer selected, and opens tabs for new ones.
+    /// Returns true if any tabs changed.
+    pub fn sync(&mut self, selected: &[InvestmentData]) -> bool {
+        let selected_refs: FxHashSet<EntryRef> = selected.iter().map(|s| s.entry_ref()).collect();
+        let mut changed = false;
+        self.state.retain_tabs(|tab| {
+            let keep = selected_refs.contains(&tab.entry);
+            changed |= !keep;
+            keep
+        });
+
+        let open: FxHashSet<EntryRef> = self.entries().into_iter().collect();
+        for entry in selected {
+            if !open.contains(&entry.entry_ref()) {
+                self.state.push_to_focused_leaf(DetailTab {
+                    entry: entry.entry_ref(),
+                    data: Some(entry.clone()),
+                });
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    pub fn show(
+        &mut self,
+        ui: &mut egui::Ui,
+        texture_cache: TextureCache,
+        bookmarks: &mut Bookmarks,
+    ) {
+        DockArea::new(&mut self.state)
+            .id(Id::new("detail_dock"))
+            .style(Style::from_egui(ui.style().as_ref()))
+            .show_inside(
+                ui,
+                &mut DetailViewer {
+                    texture_cache,
+                    bookmarks,
+                },
+            );
+    }
+
+    /// Draws a menu to save the current layout by name, or open a saved one.
+    /// Returns true if a saved layout was opened, replacing the open tabs.
+    pub fn layouts_menu(&mut self, ui: &mut egui::Ui) -> bool {
+        let mut opened = false;
+        ui.menu_button("Layouts", |ui| {
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.new_layout_name)
+                        .hint_text("Layout name")
+                        .desired_width(140.0),
+                );
+                let name = self.new_layout_name.trim();
+                if ui
+                    .add_enabled(!name.is_empty(), egui::Button::new("Save"))
+                    .clicked()
+                {
+                    self.layouts.insert(name.to_string(), self.state.clone());
+                    self.new_layout_name.clear();
+                }
+            });
+
+            if !self.layouts.is_empty() {
+                ui.separator();
+            }
+            let mut to_remove = None;
+            for (name, layout) in &self.layouts {
+                ui.horizontal(|ui| {
+                    if ui.button(name).clicked() {
+                        self.state = layout.clone();
+                        opened = true;
+                        ui.close();
+                    }
+                    if ui
+                        .small_button("\u{E000}")
+                        .on_hover_text("Delete")
+                        .clicked()
+                    {
+                        to_remove = Some(name.clone());
+                    }
+                });
+            }
+            if let Some(name) = to_remove {
+                self.layouts.remove(&name);
+            }
+        });
+        opened
+    }
+}
+
+struct DetailViewer<'a> {
+    texture_cache: TextureCache,
+    bookmarks: &'a mut Bookmarks,
+}
+
+impl TabViewer for DetailViewer<'_> {
+    type Tab = DetailTab;
+
+    fn title(&mut self, tab: &mut Self::Tab) -> WidgetText {
+        match tab.data() {
+            Some(data) => data.name().into(),
+            None => tab.entry.hash.to_string().into(),
+        }
+    }
+
+    fn id(&mut self, tab: &mut Self::Tab) -> Id {
+        Id::new(("detail_tab", tab.entry))
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, tab: &mut Self::Tab) {
+        let entry = tab.entry;
+        let Some(data) = tab.data() else {
+            ui.label(format!(
+                "{} {} could not be found",
+                tab.entry.kind, tab.entry.hash
+            ));
+            return;
+        };
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            ui.horizontal(|ui| {
+                self.bookmarks.star_button(ui, entry);
+                // TODO: show icon here
+                ui.label(data.name());
+                let enum_type: &'static str = data.into();
+                ui.label(enum_type);
+                ui.label(data.hash().to_string());
+            });
+            data.show(self.texture_cache.clone(), data.hash(), ui);
+        });
+    }
+}
@@ -0,0 +1,250 @@
+use egui::RichText;
+use tiger_investment::EntryRef;
+
+use crate::gui::common::EntryNames;
+
+/// How many steps are kept before the oldest ones are forgotten.
+const MAX_STEPS: usize = 100;
+/// How many breadcrumbs are shown on either side of the current step.
+const BREADCRUMB_RANGE: usize = 5;
+
+/// Browser-style back and forward history of the selected entries. Saved between sessions.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(from = "SavedHistory")]
+pub struct History {
+    /// Every selection that has been shown, oldest first
+    steps: Vec<Vec<EntryRef>>,
+    /// Index of the step being shown
+    position: usize,
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self {
+            steps: vec![Vec::new()],
+            position: 0,
+        }
+    }
+}
+
+/// History as it was saved, which may be empty or point past its steps.
+#[derive(Default, serde::Deserialize)]
+#[serde(default)]
+struct SavedHistory {
+    steps: Vec<Vec<EntryRef>>,
+    position: usize,
+}
+
+impl From<SavedHistory> for History {
+    fn from(saved: SavedHistory) -> Self {
+        let mut steps = saved.steps;
+        let dropped = steps.len().saturating_sub(MAX_STEPS);
+        steps.drain(..dropped);
+        if steps.is_empty() {
+            steps.push(Vec::new());
+        }
+        let position = saved.position.saturating_sub(dropped).min(steps.len() - 1);
+        Self { steps, position }
+    }
+}
+
+impl History {
+    /// The entries selected at the current step.
+    pub fn current(&self) -> &[EntryRef] {
+        self.steps
+            .get(self.position)
+            .map(|s| s.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Adds `selection` as a new step if it differs from the current one,
+    /// dropping the steps that could be gone forward to.
+    pub fn record(&mut self, selection: Vec<EntryRef>) {
+        if selection == self.current() {
+            return;
+        }
+
+        self.steps.truncate(self.position + 1);
+        self.steps.push(selection);
+        if self.steps.len() > MAX_STEPS {
+            self.steps.remove(0);
+        }
+        self.position = self.steps.len() - 1;
+    }
+
+    pub fn can_go_back(&self) -> bool {
+        self.position > 0
+    }
+
+    pub fn can_go_forward(&self) -> bool {
+        self.position + 1 < self.steps.len()
+    }
+
+    /// Moves `offset` steps forward, or back if negative. Returns false if already at the end.
+    pub fn go(&mut self, offset: isize) -> bool {
+        let Some(position) = self
+            .position
+            .checked_add_signed(offset)
+            .filter(|p| *p < self.steps.len())
+        else {
+            return false;
+        };
+        let moved = position != self.position;
+        self.position = position;
+        moved
+    }
+
+    /// Draws the back and forward buttons, followed by a trail of the steps around the current one.
+    /// Returns the offset of the step that was clicked, if any.
+    pub fn show(&self, ui: &mut egui::Ui, names: &mut EntryNames) -> Option<isize> {
+        let mut clicked = None;
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(self.can_go_back(), egui::Button::new("⏴"))
+                .on_hover_text("Back (Alt+Left, mouse back button)")
+                .clicked()
+            {
+                clicked = Some(-1);
+            }
+            if ui
+                .add_enabled(self.can_go_forward(), egui::Button::new("⏵"))
+                .on_hover_text("Forward (Alt+Right, mouse forward button)")
+                .clicked()
+            {
+                clicked = Some(1);
+            }
+
+            let first = self.position.saturating_sub(BREADCRUMB_RANGE);
+            let last = (self.position + BREADCRUMB_RANGE).min(self.steps.len() - 1);
+            if first > 0 {
+                ui.weak("…");
+            }
+            for i in first..=last {
+                if i > first {
+                    ui.weak("›");
+                }
+                let mut text = RichText::new(step_name(&self.steps[i], names));
+                if i == self.position {
+                    text = text.strong();
+                } else if i > self.position {
+                    text = text.weak();
+                }
+                if ui.selectable_label(i == self.position, text).clicked() {
+                    clicked = Some(i as isize - self.position as isize);
+                }
+            }
+            if last + 1 < self.steps.len() {
+                ui.weak("…");
+            }
+        });
+        clicked.filter(|offset| *offset != 0)
+    }
+}
+
+/// Names a step after the entry opened last, which is usually the one that was navigated to.
+fn step_name(step: &[EntryRef], names: &mut EntryNames) -> String {
+    match step.last() {
+        None => "Nothing selected".to_string(),
+        Some(entry) => {
+            let name = names
+                .get(*entry)
+                .map(str::to_string)
+                .unwrap_or_else(|| entry.hash.to_string());
+            if step.len() > 1 {
+                format!("{name} (+{})", step.len() - 1)
+            } else {
+                name
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tiger_investment::InvestmentKind;
+
+    use super::*;
+
+    fn step(hash: u32) -> Vec<EntryRef> {
+        vec![EntryRef {
+            kind: InvestmentKind::InventoryItem,
+            hash,
+        }]
+    }
+
+    #[test]
+    fn record_drops_forward_steps() {
+        let mut history = History::default();
+        history.record(step(1));
+        history.record(step(2));
+        history.record(step(3));
+        assert!(history.go(-2));
+        assert_eq!(history.current(), step(1));
+
+        history.record(step(4));
+        assert_eq!(history.current(), step(4));
+        assert!(!history.can_go_forward());
+        assert!(history.go(-1));
+        assert_eq!(history.current(), step(1));
+    }
+
+    #[test]
+    fn record_skips_the_current_selection() {
+        let mut history = History::default();
+        history.record(step(1));
+        history.record(step(1));
+        assert_eq!(history.steps.len(), 2);
+    }
+
+    #[test]
+    fn record_caps_the_steps() {
+        let mut history = History::default();
+        for hash in 0..MAX_STEPS as u32 * 2 {
+            history.record(step(hash));
+        }
+        assert_eq!(history.steps.len(), MAX_STEPS);
+        assert_eq!(history.position, MAX_STEPS - 1);
+        assert_eq!(history.current(), step(MAX_STEPS as u32 * 2 - 1));
+        assert_eq!(history.steps[0], step(MAX_STEPS as u32));
+    }
+
+    #[test]
+    fn go_stays_in_bounds() {
+        let mut history = History::default();
+        history.record(step(1));
+        assert!(!history.go(1));
+        assert!(!history.go(-2));
+        assert_eq!(history.current(), step(1));
+        assert!(history.go(-1));
+        assert!(!history.go(-1));
+        assert!(history.current().is_empty());
+        assert!(!history.go(0));
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut history = History::default();
+        history.record(step(1));
+        history.record(step(2));
+        history.go(-1);
+        let json = serde_json::to_string(&history).unwrap();
+        assert_eq!(serde_json::from_str::<History>(&json).unwrap(), history);
+    }
+
+    #[test]
+    fn empty_or_corrupt_history_is_fixed_up() {
+        for json in [
+            r#"{}"#,
+            r#"{"steps":[],"position":0}"#,
+            r#"{"steps":[],"position":5}"#,
+        ] {
+            let history: History = serde_json::from_str(json).unwrap();
+            assert_eq!(history, History::default(), "{json}");
+        }
+
+        let history: History = serde_json::from_str(r#"{"steps":[[],[]],"position":7}"#).unwrap();
+        assert_eq!(history.position, 1);
+        assert!(history.can_go_back());
+        assert!(!history.can_go_forward());
+    }
+}
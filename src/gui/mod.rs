@@ -3,38 +3,96 @@ use std::{
     time::{Duration, Instant},
 };
 
-use egui::{Align2, CollapsingHeader, Color32, CornerRadius, RichText, Stroke, Vec2};
-use log::info;
+use egui::{Align2, Color32, CornerRadius, Stroke, Vec2};
+use log::{error, info};
 use strum::IntoEnumIterator;
 use tiger_investment::{
-    InvestmentData,
-    data::image::{ColorblindMode, IconContainerData, IconContainerType},
+    InvestmentData, InvestmentKind,
+    data::{
+        character::DestinyClass,
+        image::{ColorblindMode, IconContainerData, IconContainerType},
+    },
     global_instance::{
         initialize_investment_manager, investment_manager, investment_manager_checked,
     },
     manager::InvestmentManager,
+    search::{
+        SEARCHABLE_KINDS, SearchFilter,
+        hash::{HashQuery, parse_tag_hash},
+        pipeline::{SearchPipeline, SearchSink},
+        query::{Query, QueryError},
+        text::TextQuery,
+    },
 };
 use tiger_text::Language;
 
 use crate::gui::{
-    common::DisplayUi,
+    bookmarks::Bookmarks,
+    common::{EntryNames, take_open_requests},
+    compare::CompareWindow,
+    dock::DetailDock,
+    history::History,
+    results::{ResultList, ResultView, is_selected},
+    table::{ResultTable, TableLayout},
     texture::{cache::TextureCache, icon_container},
 };
 
+mod bookmarks;
 mod common;
+mod compare;
+mod dock;
+mod history;
+mod results;
+mod table;
 mod texture;
 
+const QUERY_HELP: &str = "Search by name, by the start of a decimal or hex hash (0x1234AB),\n\
+    by a package tag (80A0B1C2, Hash64(0123456789ABCDEF)), or with a query like\n\
+    type:item name:\"ace of\" tier:exotic hash:0x1234ABCD\n\
+    Terms can be combined with AND, OR, NOT and parentheses.";
+
+/// Storage key of the kinds and categories ticked in the type filter.
+const TYPE_FILTER_KEY: &str = "type_filter";
+/// Storage key of whether results are shown as cards or a table.
+const RESULT_VIEW_KEY: &str = "result_view";
+/// Storage key of the columns and sort of the result table.
+const TABLE_LAYOUT_KEY: &str = "table_layout";
+/// Storage key of the back and forward history of the selection.
+const HISTORY_KEY: &str = "history";
+/// Storage key of the open detail tabs and the layouts saved by name.
+const DETAIL_DOCK_KEY: &str = "detail_dock";
+/// Storage key of the starred entries and collections.
+const BOOKMARKS_KEY: &str = "bookmarks";
+
+/// How many results can wait to be shown before searches have to wait for the GUI.
+const SEARCH_CHANNEL_CAPACITY: usize = 4096;
+/// How long each frame may spend taking in search results.
+const RECEIVE_BUDGET: Duration = Duration::from_millis(4);
+
 pub struct TachyscopeApp {
     // string_containers: Vec<LocalizedStrings>,
     language: Language,
-    results: Vec<InvestmentData>,
-    search_send: Arc<crossbeam::channel::Sender<InvestmentData>>,
-    search_recv: crossbeam::channel::Receiver<InvestmentData>,
+    results: ResultList,
+    result_view: ResultView,
+    result_table: ResultTable,
+    search: SearchPipeline,
     last_update_time: Instant,
     search_changed: bool,
     search_input: String,
+    search_filter: SearchFilter,
+    group_by_category: bool,
+    full_text: bool,
+    /// Syntax error of the current query, shown next to the search bar.
+    query_error: Option<QueryError>,
     selected: Vec<InvestmentData>,
-    to_deselect: Vec<usize>,
+    detail_dock: DetailDock,
+    compare: Option<CompareWindow>,
+    bookmarks: Bookmarks,
+    history: History,
+    /// Names of the entries listed in the bookmarks, history and compare window
+    entry_names: EntryNames,
+    /// Set until the selection saved in the history has been opened again, once the investment system is loaded.
+    restore_selection: bool,
     texture_cache: TextureCache,
 }
 
@@ -99,8 +157,6 @@ impl TachyscopeApp {
 
         cc.egui_ctx.set_fonts(fonts);
 
-        let (search_send, search_recv) = crossbeam::channel::bounded(500);
-
         std::thread::spawn(|| {
             let investment = Arc::new(InvestmentManager::new().unwrap());
             initialize_investment_manager(&investment);
@@ -108,21 +164,241 @@ impl TachyscopeApp {
 
         TachyscopeApp {
             language: Language::English,
-            results: Vec::new(),
-            search_send: Arc::new(search_send),
-            search_recv,
+            results: ResultList::default(),
+            result_view: cc
+                .storage
+                .and_then(|s| eframe::get_value(s, RESULT_VIEW_KEY))
+                .unwrap_or_default(),
+            result_table: ResultTable::new(
+                cc.storage
+                    .and_then(|s| eframe::get_value::<TableLayout>(s, TABLE_LAYOUT_KEY))
+                    .unwrap_or_default(),
+            ),
+            search: SearchPipeline::new(SEARCH_CHANNEL_CAPACITY),
             last_update_time: Instant::now(),
-            search_changed: false,
+            // Lists everything once the investment system has loaded
+            search_changed: true,
             search_input: String::new(),
+            search_filter: SearchFilter {
+                types: cc
+                    .storage
+                    .and_then(|s| eframe::get_value(s, TYPE_FILTER_KEY))
+                    .unwrap_or_default(),
+                ..Default::default()
+            },
+            group_by_category: false,
+            full_text: false,
+            query_error: None,
             selected: Vec::new(),
-            to_deselect: Vec::new(),
+            detail_dock: cc
+                .storage
+                .and_then(|s| eframe::get_value(s, DETAIL_DOCK_KEY))
+                .unwrap_or_default(),
+            history: cc
+                .storage
+                .and_then(|s| eframe::get_value(s, HISTORY_KEY))
+                .unwrap_or_default(),
+            entry_names: EntryNames::default(),
+            restore_selection: true,
+            compare: None,
+            bookmarks: cc
+                .storage
+                .and_then(|s| eframe::get_value(s, BOOKMARKS_KEY))
+                .unwrap_or_default(),
             texture_cache: TextureCache::new(cc.wgpu_render_state.clone().unwrap()),
         }
     }
 }
 
-// TODO: checkbox grid to filter types
+impl TachyscopeApp {
+    /// Re-parses the search input, if it's a query, to show any syntax errors.
+    fn update_query_error(&mut self) {
+        self.query_error = if parse_tag_hash(&self.search_input).is_some() {
+            None
+        } else if self.full_text {
+            TextQuery::parse(&self.search_input).err()
+        } else if Query::is_query(&self.search_input) {
+            Query::parse(&self.search_input).err()
+        } else {
+            None
+        };
+    }
+
+    /// Draws the facets of the search bar. Returns true if any of them changed.
+    fn search_facets(&mut self, ui: &mut egui::Ui) -> bool {
+        let mut changed = self.destination_facet(ui);
+        changed |= self.category_facet(ui);
+
+        egui::ComboBox::from_id_salt("class_facet")
+            .selected_text(
+                self.search_filter
+                    .class
+                    .map(|c| c.to_string())
+                    .unwrap_or_else(|| "Any class".to_string()),
+            )
+            .show_ui(ui, |ui| {
+                changed |= ui
+                    .selectable_value(&mut self.search_filter.class, None, "Any class")
+                    .changed();
+                for class in DestinyClass::iter() {
+                    changed |= ui
+                        .selectable_value(
+                            &mut self.search_filter.class,
+                            Some(class),
+                            class.to_string(),
+                        )
+                        .changed();
+                }
+            });
+
+        changed
+    }
+
+    /// Draws a checkbox for every kind of entry, and every root item category.
+    /// Returns true if any of them changed.
+    fn type_filter(&mut self, ui: &mut egui::Ui) -> bool {
+        let types = &mut self.search_filter.types;
+        let mut changed = false;
+        egui::Grid::new("type_filter_kinds").show(ui, |ui| {
+            for kind in SEARCHABLE_KINDS {
+                let mut shown = types.allows_kind(kind);
+                if ui.checkbox(&mut shown, kind.to_string()).changed() {
+                    if shown {
+                        types.hidden_kinds.remove(&kind);
+                    } else {
+                        types.hidden_kinds.insert(kind);
+                    }
+                    changed = true;
+                }
+            }
+        });
+
+        if types.allows_kind(InvestmentKind::InventoryItem) {
+            let mut roots: Vec<(String, u32)> = investment_manager()
+                .categories()
+                .roots()
+                .iter()
+                .filter(|c| c.data.name.valid())
+                .map(|c| (c.name(), c.data.hash))
+                .collect();
+            roots.sort();
+
+            ui.label("Item categories");
+            egui::Grid::new("type_filter_categories").show(ui, |ui| {
+                for (i, (name, hash)) in roots.into_iter().enumerate() {
+                    let mut shown = !types.hidden_categories.contains(&hash);
+                    if ui.checkbox(&mut shown, name).changed() {
+                        if shown {
+                            types.hidden_categories.remove(&hash);
+                        } else {
+                            types.hidden_categories.insert(hash);
+                        }
+                        changed = true;
+                    }
+                    if (i + 1) % 6 == 0 {
+                        ui.end_row();
+                    }
+                }
+            });
+        }
+
+        changed
+    }
+
+    /// Draws the category facet of the search bar. Returns true if the selected category changed.
+    fn category_facet(&mut self, ui: &mut egui::Ui) -> bool {
+        let categories = investment_manager().categories();
+        let selected_text = self
+            .search_filter
+            .category
+            .and_then(|hash| categories.get_by_hash(hash))
+            .map(|c| c.display_path())
+            .unwrap_or_else(|| "Any category".to_string());
+
+        let mut changed = false;
+        egui::ComboBox::from_id_salt("category_facet")
+            .selected_text(selected_text)
+            .height(400.0)
+            .show_ui(ui, |ui| {
+                changed |= ui
+                    .selectable_value(&mut self.search_filter.category, None, "Any category")
+                    .changed();
+                let mut paths: Vec<(String, u32)> = categories
+                    .categories()
+                    .iter()
+                    .filter(|c| c.data.name.valid())
+                    .map(|c| (c.display_path(), c.data.hash))
+                    .collect();
+                paths.sort();
+                for (path, hash) in paths {
+                    changed |= ui
+                        .selectable_value(&mut self.search_filter.category, Some(hash), path)
+                        .changed();
+                }
+            });
+        changed
+    }
+
+    /// Draws the destination facet of the search bar. Returns true if the selected destination changed.
+    fn destination_facet(&mut self, ui: &mut egui::Ui) -> bool {
+        let destinations = investment_manager().destinations();
+        let selected_text = self
+            .search_filter
+            .destination
+            .and_then(|hash| destinations.get_by_hash(hash))
+            .map(|d| d.name())
+            .unwrap_or_else(|| "Any destination".to_string());
+
+        let mut changed = false;
+        egui::ComboBox::from_id_salt("destination_facet")
+            .selected_text(selected_text)
+            .show_ui(ui, |ui| {
+                changed |= ui
+                    .selectable_value(&mut self.search_filter.destination, None, "Any destination")
+                    .changed();
+                for destination in destinations.destinations() {
+                    let name = destination.data.name.get().unwrap_or_default();
+                    changed |= ui
+                        .selectable_value(
+                            &mut self.search_filter.destination,
+                            Some(destination.data.hash),
+                            name,
+                        )
+                        .changed();
+                }
+            });
+        changed
+    }
+
+    /// Replaces the selection with the entries of the current history step.
+    fn show_history_step(&mut self) {
+        self.selected = self
+            .history
+            .current()
+            .iter()
+            .filter_map(|entry| entry.resolve())
+            .collect();
+    }
+}
+
+/// Runs a search on its own thread, and marks it as finished once it returns.
+fn spawn_search(sink: SearchSink, search: impl FnOnce(SearchSink) + Send + 'static) {
+    std::thread::spawn(move || {
+        search(sink.clone());
+        sink.finish();
+    });
+}
+
 impl eframe::App for TachyscopeApp {
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        eframe::set_value(storage, TYPE_FILTER_KEY, &self.search_filter.types);
+        eframe::set_value(storage, RESULT_VIEW_KEY, &self.result_view);
+        eframe::set_value(storage, TABLE_LAYOUT_KEY, &self.result_table.layout);
+        eframe::set_value(storage, HISTORY_KEY, &self.history);
+        eframe::set_value(storage, DETAIL_DOCK_KEY, &self.detail_dock);
+        eframe::set_value(storage, BOOKMARKS_KEY, &self.bookmarks);
+    }
+
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
             egui::MenuBar::new().ui(ui, |ui| {
@@ -143,10 +419,28 @@ impl eframe::App for TachyscopeApp {
                                 .selectable_value(&mut self.language, lang, lang.to_string())
                                 .changed()
                             {
-                                investment_manager().strings().set_lang(self.language);
+                                if let Err(e) = investment_manager().set_lang(self.language) {
+                                    error!("Failed to switch language: {e}");
+                                }
+                                self.entry_names.clear();
+                                self.search_changed = true;
                             }
                         }
                     });
+                ui.add_space(16.0);
+
+                let is_loaded = investment_manager_checked().is_ok();
+                ui.add_enabled_ui(is_loaded, |ui| {
+                    ui.toggle_value(&mut self.bookmarks.visible, "Bookmarks");
+                    if self.detail_dock.layouts_menu(ui) {
+                        self.selected = self
+                            .detail_dock
+                            .entries()
+                            .iter()
+                            .filter_map(|entry| entry.resolve())
+                            .collect();
+                    }
+                });
             });
         });
 
@@ -173,106 +467,221 @@ impl eframe::App for TachyscopeApp {
             is_loading_investment = true;
         }
 
+        if !is_loading_investment {
+            if self.restore_selection {
+                self.restore_selection = false;
+                self.show_history_step();
+            }
+
+            let (back, forward) = ctx.input_mut(|i| {
+                (
+                    i.pointer.button_pressed(egui::PointerButton::Extra1)
+                        || i.consume_key(egui::Modifiers::ALT, egui::Key::ArrowLeft),
+                    i.pointer.button_pressed(egui::PointerButton::Extra2)
+                        || i.consume_key(egui::Modifiers::ALT, egui::Key::ArrowRight),
+                )
+            });
+            let mut offset = match (back, forward) {
+                (true, false) => Some(-1),
+                (false, true) => Some(1),
+                _ => None,
+            };
+            egui::TopBottomPanel::top("history_panel").show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    if let Some(clicked) = self.history.show(ui, &mut self.entry_names) {
+                        offset = Some(clicked);
+                    }
+                    ui.separator();
+                    let pair = CompareWindow::for_selection(&self.selected);
+                    if ui
+                        .add_enabled(pair.is_some(), egui::Button::new("Compare"))
+                        .on_disabled_hover_text("Select two entries of the same kind")
+                        .clicked()
+                    {
+                        self.compare = pair;
+                    }
+                });
+            });
+            if let Some(compare) = &mut self.compare
+                && !compare.show(ctx, &self.selected, &mut self.entry_names)
+            {
+                self.compare = None;
+            }
+            if let Some(offset) = offset
+                && self.history.go(offset)
+            {
+                self.show_history_step();
+            }
+
+            if self.detail_dock.sync(&self.selected) {
+                ctx.request_repaint();
+            }
+            if !self.detail_dock.is_empty() {
+                egui::SidePanel::right("detail_panel")
+                    .default_width(480.0)
+                    .show(ctx, |ui| {
+                        self.detail_dock
+                            .show(ui, self.texture_cache.clone(), &mut self.bookmarks);
+                    });
+                // Closing a tab deselects its entry
+                let open = self.detail_dock.entries();
+                self.selected.retain(|s| open.contains(&s.entry_ref()));
+            }
+            if self.bookmarks.visible {
+                egui::SidePanel::left("bookmarks_panel")
+                    .default_width(260.0)
+                    .show(ctx, |ui| self.bookmarks.show(ui, &mut self.entry_names));
+            }
+        }
+
         egui::CentralPanel::default().show(ctx, |ui| {
             ui.add_enabled_ui(!is_loading_investment, |ui| {
                 if Instant::now().duration_since(self.last_update_time) > Duration::from_millis(500)
                     && self.search_changed
+                    && !is_loading_investment
                 {
                     self.search_changed = false;
-                    // Explicitly drop it, so the sender stops sending.
-                    drop(self.search_send.clone());
-                    let (search_send, search_recv) = crossbeam::channel::bounded(500);
-                    self.search_send = Arc::new(search_send);
-                    self.search_recv = search_recv;
+                    // Cancels the previous search, so its results never show up here
+                    let sink = self.search.restart();
+                    let filter = self.search_filter.clone();
                     self.results.clear();
-                    if let Ok(hash) = self.search_input.parse::<u32>() {
-                        self.results = investment_manager().search_by_hash(hash);
+                    let tag = parse_tag_hash(&self.search_input);
+                    let hash_query = HashQuery::parse(&self.search_input);
+                    if tag.is_some() || hash_query.is_some() {
+                        let name = HashQuery::could_be_name(&self.search_input)
+                            .then(|| self.search_input.clone());
+                        spawn_search(sink, move |sink| {
+                            // An 8 digit hex string can be either, so look for both
+                            if let Some(tag) = tag {
+                                investment_manager().search_by_tag(
+                                    sink.clone(),
+                                    tag,
+                                    filter.clone(),
+                                );
+                            }
+                            if let Some(query) = hash_query {
+                                investment_manager().search_by_partial_hash(
+                                    sink.clone(),
+                                    query,
+                                    filter.clone(),
+                                );
+                            }
+                            // Bare hex like "b52" may be a name too
+                            if let Some(name) = name {
+                                investment_manager().search_by_name(sink, name, filter);
+                            }
+                        });
+                    } else if self.full_text {
+                        match TextQuery::parse(&self.search_input) {
+                            Ok(query) if !query.is_empty() => spawn_search(sink, move |sink| {
+                                investment_manager().search_full_text(sink, query, filter)
+                            }),
+                            _ => sink.finish(),
+                        }
+                    } else if let Some(query) = Query::is_query(&self.search_input)
+                        .then(|| Query::parse(&self.search_input).ok())
+                        .flatten()
+                    {
+                        spawn_search(sink, move |sink| {
+                            investment_manager().search_by_query(sink, query, filter)
+                        });
                     } else {
+                        // Plain names, and queries that don't parse, are searched for by name
                         let input = self.search_input.clone();
-                        let sender = self.search_send.clone();
-                        std::thread::spawn(move || {
-                            investment_manager().search_by_name(sender, input)
+                        spawn_search(sink, move |sink| {
+                            investment_manager().search_by_name(sink, input, filter)
                         });
                     };
                 }
 
-                if !self.search_recv.is_empty()
-                    && self.results.len() < 500
-                    && let Ok(recv) = self.search_recv.recv()
-                {
-                    self.results.push(recv);
+                let received = self.results.receive(&self.search, RECEIVE_BUDGET);
+                if received || !self.search.is_finished() {
+                    // Keep pulling in results, even when nothing else happens
+                    ctx.request_repaint();
                 }
 
                 ui.horizontal(|ui| {
                     ui.label("Search:");
-                    if ui.text_edit_singleline(&mut self.search_input).changed() {
+                    let search_bar = ui
+                        .text_edit_singleline(&mut self.search_input)
+                        .on_hover_text(QUERY_HELP);
+                    if search_bar.changed() {
                         self.search_changed = true;
+                        self.update_query_error();
                     }
-                });
-                egui::ScrollArea::vertical().show(ui, |ui| {
-                    egui::Grid::new("grid_results").show(ui, |ui| {
-                        let max_cols = (ui.available_width() / 20.0).floor() as usize - 2;
-                        for (i, result) in self.results.iter().enumerate() {
-                            if i > 500 {
-                                ui.label("Only 500 results can be shown at a time.");
-                                break;
-                            }
-                            let frame = egui::containers::Frame::new()
-                                .outer_margin(egui::Margin::same(8))
-                                .inner_margin(egui::Margin::same(4))
-                                .corner_radius(egui::CornerRadius::same(2))
-                                .stroke(Stroke::new(2.0, Color32::PLACEHOLDER))
-                                .show(ui, |ui| {
-                                    ui.label(result.name());
-                                    let enum_type: &'static str = result.into();
-                                    let mut typetext = String::from(enum_type);
-                                    if let Some(itype) = result.itype() {
-                                        typetext += &format!(": {itype}");
-                                    }
-                                    ui.label(RichText::new(typetext).italics());
-                                    ui.label(result.hash().to_string())
-                                });
-                            if frame.response.contains_pointer() {
-                                ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
-                            }
-                            if frame.response.interact(egui::Sense::click()).clicked() {
-                                if self.selected.iter().any(|x| x.hash() == result.hash()) {
-                                    self.selected.retain(|x| x.hash() != x.hash());
-                                } else {
-                                    self.selected.push(result.clone());
-                                }
-                            };
-                            if (i + 1) % max_cols == 0 {
-                                ui.end_row();
-                            }
+                    if let Some(error) = &self.query_error {
+                        let color = ui.visuals().error_fg_color;
+                        ui.painter().rect_stroke(
+                            search_bar.rect,
+                            2.0,
+                            Stroke::new(1.0, color),
+                            egui::StrokeKind::Outside,
+                        );
+                        ui.colored_label(color, error.to_string());
+                    }
+                    if !is_loading_investment && self.search_facets(ui) {
+                        self.search_changed = true;
+                    }
+                    if ui
+                        .checkbox(&mut self.full_text, "Full text")
+                        .on_hover_text(
+                            "Also search descriptions, flavor text, toasts and sources.\n\
+                             Scope a word to a field with name:, item_type:, description:, \
+                             flavor:, toast: or display_source:, e.g. flavor:\"eyes up\"",
+                        )
+                        .changed()
+                    {
+                        self.search_changed = true;
+                        self.update_query_error();
+                    }
+                    ui.selectable_value(&mut self.result_view, ResultView::Cards, "Cards");
+                    ui.selectable_value(&mut self.result_view, ResultView::Table, "Table");
+                    match self.result_view {
+                        ResultView::Cards => {
+                            ui.checkbox(&mut self.group_by_category, "Group by category");
                         }
-                    });
+                        ResultView::Table => self.result_table.column_picker(ui),
+                    }
+                    if self.search.is_finished() {
+                        ui.label(format!("{} matches", self.results.len()));
+                    } else {
+                        ui.spinner();
+                        ui.label(format!("{} matches so far", self.results.len()));
+                    }
                 });
+                if !is_loading_investment {
+                    egui::CollapsingHeader::new("Types")
+                        .id_salt("type_filter")
+                        .show(ui, |ui| {
+                            if self.type_filter(ui) {
+                                // Hide what was just unticked right away, the search picks up the rest
+                                let filter = &self.search_filter;
+                                self.results.retain(|data| filter.matches(data));
+                                self.search_changed = true;
+                            }
+                        });
+                }
+                match self.result_view {
+                    ResultView::Cards => {
+                        self.results
+                            .show(ui, self.group_by_category, &mut self.selected);
+                    }
+                    ResultView::Table => {
+                        self.result_table
+                            .show(ui, &self.results, &mut self.selected);
+                    }
+                }
             });
         });
 
-        for (i, selected) in self.selected.iter().enumerate() {
-            egui::SidePanel::right(format!("selection_panel_{i}")).show(ctx, |ui| {
-                egui::ScrollArea::vertical().show(ui, |ui| {
-                    ui.horizontal(|ui| {
-                        if ui.button("\u{E000}").clicked() {
-                            self.to_deselect.push(i);
-                        }
-                        // TODO: show icon here
-                        ui.label(selected.name());
-                        let enum_type: &'static str = selected.into();
-                        ui.label(enum_type);
-                        ui.label(selected.hash().to_string());
-                    });
-                    selected.show(self.texture_cache.clone(), selected.hash(), ui);
-                });
-            });
-        }
-        if !self.to_deselect.is_empty() {
-            for index in &self.to_deselect {
-                self.selected.remove(*index);
+        for entry in take_open_requests(ctx) {
+            if !is_selected(&self.selected, &entry) {
+                self.selected.push(entry);
             }
-            self.to_deselect.clear();
+        }
+        if !is_loading_investment {
+            self.history
+                .record(self.selected.iter().map(|s| s.entry_ref()).collect());
         }
     }
 }
@@ -0,0 +1,437 @@
+use std::{
+    cmp::Reverse,
+    collections::BTreeMap,
+    ops::Range,
+    time::{Duration, Instant},
+};
+
+use egui::{Rect, RichText, Sense, Stroke, UiBuilder, Vec2, vec2};
+use rustc_hash::FxHashSet;
+use tiger_investment::{
+    InvestmentData,
+    search::{SearchResult, pipeline::SearchPipeline},
+};
+
+/// Size of a result card, without the space around it.
+const CARD_SIZE: Vec2 = vec2(200.0, 56.0);
+const CARD_SPACING: f32 = 8.0;
+const HEADER_HEIGHT: f32 = 24.0;
+
+/// How the results are drawn. Saved between sessions.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum ResultView {
+    #[default]
+    Cards,
+    Table,
+}
+
+/// Results of the current search, best match first, drawn as a virtualized grid of cards.
+#[derive(Default)]
+pub struct ResultList {
+    results: Vec<SearchResult>,
+    /// Indices into `results` by category, built when first needed after the results change.
+    groups: Option<Vec<ResultGroup>>,
+    /// Names of the groups that have been folded away
+    collapsed: FxHashSet<String>,
+    /// Goes up whenever the results change, so views can tell when to redo their own sorting.
+    revision: u64,
+    /// Set when the last change only merged in new results
+    last_merge: Option<Merge>,
+}
+
+/// Where a batch of results went when it was merged in, so views can update their own order
+/// instead of redoing it.
+pub struct Merge {
+    /// Revision of the results the batch was merged into
+    pub revision: u64,
+    /// New index of each result that was already there
+    pub moved: Vec<usize>,
+    /// Indices of the new results, in ascending order
+    pub inserted: Vec<usize>,
+}
+
+struct ResultGroup {
+    name: String,
+    results: Vec<usize>,
+}
+
+/// One line of the grid. Only the lines in view are drawn.
+enum Row {
+    Header(usize),
+    Cards {
+        group: Option<usize>,
+        range: Range<usize>,
+    },
+}
+
+impl ResultList {
+    pub fn clear(&mut self) {
+        self.results.clear();
+        self.changed();
+    }
+
+    fn changed(&mut self) {
+        self.groups = None;
+        self.revision += 1;
+        self.last_merge = None;
+    }
+
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// How the last batch was merged in, if nothing else changed the results since.
+    pub fn last_merge(&self) -> Option<&Merge> {
+        self.last_merge.as_ref()
+    }
+
+    pub fn results(&self) -> &[SearchResult] {
+        &self.results
+    }
+
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+
+    /// Drops the results `f` returns false for.
+    pub fn retain(&mut self, mut f: impl FnMut(&InvestmentData) -> bool) {
+        self.results.retain(|r| f(&r.data));
+        self.changed();
+    }
+
+    /// Takes results from `search` for up to `budget`, keeping them sorted by score.
+    /// Returns true if any arrived.
+    pub fn receive(&mut self, search: &SearchPipeline, budget: Duration) -> bool {
+        let start = Instant::now();
+        let mut batch = Vec::new();
+        while start.elapsed() < budget
+            && let Some(result) = search.try_recv()
+        {
+            batch.push(result);
+        }
+        if batch.is_empty() {
+            return false;
+        }
+
+        let merge = self.merge(batch);
+        if let Some(groups) = &mut self.groups {
+            merge_groups(groups, &self.results, &merge);
+        }
+        self.revision += 1;
+        self.last_merge = Some(merge);
+        true
+    }
+
+    /// Merges `batch` into the results, keeping them sorted by score.
+    /// Results with the same score stay in the order they arrived.
+    fn merge(&mut self, mut batch: Vec<SearchResult>) -> Merge {
+        batch.sort_by_key(|r| Reverse(r.score));
+
+        let old = std::mem::take(&mut self.results);
+        let mut moved = Vec::with_capacity(old.len());
+        let mut inserted = Vec::with_capacity(batch.len());
+        self.results.reserve(old.len() + batch.len());
+        let mut batch = batch.into_iter().peekable();
+        for result in old {
+            while let Some(new) = batch.next_if(|new| new.score > result.score) {
+                inserted.push(self.results.len());
+                self.results.push(new);
+            }
+            moved.push(self.results.len());
+            self.results.push(result);
+        }
+        for new in batch {
+            inserted.push(self.results.len());
+            self.results.push(new);
+        }
+
+        Merge {
+            revision: self.revision,
+            moved,
+            inserted,
+        }
+    }
+
+    /// Draws the results as a grid of cards. Clicking a card toggles its selection.
+    pub fn show(
+        &mut self,
+        ui: &mut egui::Ui,
+        group_by_category: bool,
+        selected: &mut Vec<InvestmentData>,
+    ) {
+        if !group_by_category {
+            self.groups = None;
+        } else if self.groups.is_none() {
+            self.groups = Some(self.build_groups());
+        }
+
+        let cell = CARD_SIZE + Vec2::splat(CARD_SPACING);
+        let columns = ((ui.available_width() / cell.x).floor() as usize).max(1);
+        let rows = self.rows(group_by_category, columns);
+        let mut row_ends = Vec::with_capacity(rows.len());
+        let mut height = 0.0;
+        for row in &rows {
+            height += match row {
+                Row::Header(_) => HEADER_HEIGHT,
+                Row::Cards { .. } => cell.y,
+            };
+            row_ends.push(height);
+        }
+
+        let mut toggled_group = None;
+        egui::ScrollArea::vertical()
+            .auto_shrink(false)
+            .show_viewport(ui, |ui, viewport| {
+                ui.set_height(height);
+                let origin = ui.max_rect().min;
+                let first = row_ends.partition_point(|end| *end < viewport.min.y);
+                for (i, row) in rows.iter().enumerate().skip(first) {
+                    let top = if i == 0 { 0.0 } else { row_ends[i - 1] };
+                    if top > viewport.max.y {
+                        break;
+                    }
+                    let row_rect = Rect::from_min_max(
+                        origin + vec2(0.0, top),
+                        origin + vec2(ui.max_rect().width(), row_ends[i]),
+                    );
+
+                    match row {
+                        Row::Header(g) => {
+                            let group = &self.groups.as_ref().unwrap()[*g];
+                            let open = !self.collapsed.contains(&group.name);
+                            let text = format!(
+                                "{} {} ({})",
+                                if open { "⏷" } else { "⏵" },
+                                group.name,
+                                group.results.len()
+                            );
+                            ui.scope_builder(UiBuilder::new().max_rect(row_rect), |ui| {
+                                if ui
+                                    .add(
+                                        egui::Label::new(RichText::new(text).strong())
+                                            .sense(Sense::click()),
+                                    )
+                                    .clicked()
+                                {
+                                    toggled_group = Some(group.name.clone());
+                                }
+                            });
+                        }
+                        Row::Cards { group, range } => {
+                            for (column, index) in range.clone().enumerate() {
+                                let index = match group {
+                                    Some(g) => self.groups.as_ref().unwrap()[*g].results[index],
+                                    None => index,
+                                };
+                                let card_rect = Rect::from_min_size(
+                                    row_rect.min + vec2(column as f32 * cell.x, 0.0),
+                                    CARD_SIZE,
+                                );
+                                result_card(ui, card_rect, &self.results[index].data, selected);
+                            }
+                        }
+                    }
+                }
+            });
+
+        if let Some(group) = toggled_group
+            && !self.collapsed.remove(&group)
+        {
+            self.collapsed.insert(group);
+        }
+    }
+
+    fn build_groups(&self) -> Vec<ResultGroup> {
+        let mut groups: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+        for (i, result) in self.results.iter().enumerate() {
+            groups
+                .entry(result_group(&result.data))
+                .or_default()
+                .push(i);
+        }
+        groups
+            .into_iter()
+            .map(|(name, results)| ResultGroup { name, results })
+            .collect()
+    }
+
+    fn rows(&self, group_by_category: bool, columns: usize) -> Vec<Row> {
+        let card_rows = |group: Option<usize>, len: usize| {
+            (0..len.div_ceil(columns)).map(move |r| Row::Cards {
+                group,
+                range: r * columns..((r + 1) * columns).min(len),
+            })
+        };
+
+        match self.groups.as_ref() {
+            Some(groups) if group_by_category => {
+                let mut rows = Vec::new();
+                for (g, group) in groups.iter().enumerate() {
+                    rows.push(Row::Header(g));
+                    if !self.collapsed.contains(&group.name) {
+                        rows.extend(card_rows(Some(g), group.results.len()));
+                    }
+                }
+                rows
+            }
+            _ => card_rows(None, self.results.len()).collect(),
+        }
+    }
+}
+
+fn result_card(
+    ui: &mut egui::Ui,
+    rect: Rect,
+    result: &InvestmentData,
+    selected: &mut Vec<InvestmentData>,
+) {
+    let is_selected = is_selected(selected, result);
+    let stroke = if is_selected {
+        ui.visuals().selection.stroke
+    } else {
+        Stroke::new(2.0, ui.visuals().widgets.noninteractive.bg_stroke.color)
+    };
+    ui.painter()
+        .rect_stroke(rect, 2.0, stroke, egui::StrokeKind::Inside);
+
+    ui.scope_builder(UiBuilder::new().max_rect(rect.shrink(6.0)), |ui| {
+        ui.add(egui::Label::new(result.name()).truncate());
+        let enum_type: &'static str = result.into();
+        let mut typetext = String::from(enum_type);
+        if let Some(itype) = result.itype() {
+            typetext += &format!(": {itype}");
+        }
+        ui.add(egui::Label::new(RichText::new(typetext).italics()).truncate());
+        ui.label(result.hash().to_string());
+    });
+
+    let response = ui.interact(
+        rect,
+        ui.id().with(("result", result.entry_ref())),
+        Sense::click(),
+    );
+    if response.hovered() {
+        ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+    }
+    if response.clicked() {
+        toggle_selected(selected, result);
+    }
+}
+
+/// Returns true if `entry` is one of the `selected` entries.
+/// Entries of different kinds can share a hash, so they are matched by kind and hash.
+pub fn is_selected(selected: &[InvestmentData], entry: &InvestmentData) -> bool {
+    let entry = entry.entry_ref();
+    selected.iter().any(|x| x.entry_ref() == entry)
+}
+
+/// Selects `result`, or deselects it if it already was.
+pub fn toggle_selected(selected: &mut Vec<InvestmentData>, result: &InvestmentData) {
+    if is_selected(selected, result) {
+        let entry = result.entry_ref();
+        selected.retain(|x| x.entry_ref() != entry);
+    } else {
+        selected.push(result.clone());
+    }
+}
+
+/// Returns the name of the group a result is shown under when grouping by category.
+/// Moves the group indices to where `merge` put their results, and adds the new results.
+fn merge_groups(groups: &mut Vec<ResultGroup>, results: &[SearchResult], merge: &Merge) {
+    for group in groups.iter_mut() {
+        for i in &mut group.results {
+            *i = merge.moved[*i];
+        }
+    }
+    for &i in &merge.inserted {
+        let name = result_group(&results[i].data);
+        match groups.binary_search_by(|g| g.name.cmp(&name)) {
+            Ok(g) => {
+                let group = &mut groups[g].results;
+                group.insert(group.partition_point(|r| *r < i), i);
+            }
+            Err(g) => groups.insert(
+                g,
+                ResultGroup {
+                    name,
+                    results: vec![i],
+                },
+            ),
+        }
+    }
+}
+
+fn result_group(result: &InvestmentData) -> String {
+    match result {
+        InvestmentData::InventoryItem(i) => i
+            .primary_category()
+            .map(|c| c.display_path())
+            .unwrap_or_else(|| "Uncategorized".to_string()),
+        _ => {
+            let enum_type: &'static str = result.into();
+            enum_type.to_string()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tiger_investment::data::activity::graph::SActivityGraph;
+
+    use super::*;
+
+    /// A result whose generation stands in for the order it arrived in.
+    fn result(score: u32, arrived: u64) -> SearchResult {
+        let data = if arrived.is_multiple_of(2) {
+            InvestmentData::Achievement
+        } else {
+            InvestmentData::ActivityGraph(Box::new(SActivityGraph {
+                file_size: 0,
+                nodes: Vec::new(),
+            }))
+        };
+        SearchResult {
+            data,
+            score,
+            generation: arrived,
+        }
+    }
+
+    fn arrival_order(list: &ResultList) -> Vec<u64> {
+        list.results.iter().map(|r| r.generation).collect()
+    }
+
+    #[test]
+    fn merge_keeps_score_then_arrival_order() {
+        let mut list = ResultList::default();
+        list.merge(vec![result(5, 0), result(9, 1), result(5, 2)]);
+        assert_eq!(arrival_order(&list), [1, 0, 2]);
+
+        let merge = list.merge(vec![result(5, 3), result(10, 4), result(1, 5)]);
+        assert_eq!(arrival_order(&list), [4, 1, 0, 2, 3, 5]);
+        assert_eq!(merge.moved, [1, 2, 3]);
+        assert_eq!(merge.inserted, [0, 4, 5]);
+    }
+
+    #[test]
+    fn merged_groups_match_rebuilt_groups() {
+        let mut list = ResultList::default();
+        list.merge(vec![result(5, 0), result(9, 2), result(3, 4)]);
+        let mut groups = list.build_groups();
+        assert_eq!(groups.len(), 1);
+
+        let merge = list.merge(vec![result(7, 1), result(9, 6), result(1, 3)]);
+        merge_groups(&mut groups, &list.results, &merge);
+
+        let rebuilt = list.build_groups();
+        assert_eq!(groups.len(), rebuilt.len());
+        for (group, expected) in groups.iter().zip(&rebuilt) {
+            assert_eq!(group.name, expected.name);
+            assert_eq!(group.results, expected.results);
+        }
+    }
+}
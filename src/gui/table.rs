@@ -0,0 +1,274 @@
+use std::fmt;
+
+use egui::{Align, Label, Layout, RichText, Sense};
+use egui_extras::{Column as TableColumn, TableBuilder};
+use strum::IntoEnumIterator;
+use tiger_investment::{InvestmentData, global_instance::investment_manager};
+
+use crate::gui::results::{ResultList, is_selected, toggle_selected};
+
+const ROW_HEIGHT: f32 = 20.0;
+
+/// A column of the result table.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    strum::Display,
+    strum::EnumIter,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub enum Column {
+    Name,
+    Kind,
+    #[strum(to_string = "Item Type")]
+    ItemType,
+    Hash,
+    Index,
+    Tier,
+    #[strum(to_string = "Max Party")]
+    MaxParty,
+}
+
+impl Column {
+    fn value(self, data: &InvestmentData) -> CellValue {
+        match self {
+            Self::Name => CellValue::Text(data.name()),
+            Self::Kind => CellValue::Text(data.kind().to_string()),
+            Self::ItemType => data.itype().map_or(CellValue::Empty, CellValue::Text),
+            Self::Hash => CellValue::Number(data.hash() as u64),
+            Self::Index => investment_manager()
+                .index_of(data.entry_ref())
+                .map_or(CellValue::Empty, |i| CellValue::Number(i as u64)),
+            Self::Tier => match data {
+                InvestmentData::InventoryItem(i) => i.tier().map_or(CellValue::Empty, |t| {
+                    CellValue::Labeled(t as u64, t.to_string())
+                }),
+                _ => CellValue::Empty,
+            },
+            Self::MaxParty => match data {
+                InvestmentData::Activity(a) => {
+                    CellValue::Number(a.data.matchmaking.max_party as u64)
+                }
+                _ => CellValue::Empty,
+            },
+        }
+    }
+}
+
+/// What a cell shows, and sorts by. Empty cells sort first.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum CellValue {
+    Empty,
+    Number(u64),
+    Text(String),
+    /// Sorted by the number, shown as the text
+    Labeled(u64, String),
+}
+
+impl fmt::Display for CellValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => Ok(()),
+            Self::Number(n) => write!(f, "{n}"),
+            Self::Text(s) | Self::Labeled(_, s) => f.write_str(s),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ColumnSort {
+    pub column: Column,
+    pub ascending: bool,
+}
+
+/// The columns shown in the result table, and what it's sorted by. Saved between sessions.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct TableLayout {
+    pub columns: Vec<Column>,
+    /// `None` keeps the results ordered by how well they matched
+    pub sort: Option<ColumnSort>,
+}
+
+impl Default for TableLayout {
+    fn default() -> Self {
+        Self {
+            columns: vec![
+                Column::Name,
+                Column::Kind,
+                Column::ItemType,
+                Column::Hash,
+                Column::Index,
+            ],
+            sort: None,
+        }
+    }
+}
+
+/// Draws the results as a table, one row per result.
+#[derive(Default)]
+pub struct ResultTable {
+    pub layout: TableLayout,
+    /// Row order for a sort, along with the revision of the results it was made for
+    order: Option<(u64, ColumnSort, Vec<usize>)>,
+}
+
+impl ResultTable {
+    pub fn new(layout: TableLayout) -> Self {
+        Self {
+            layout,
+            order: None,
+        }
+    }
+
+    /// Draws a menu to pick the shown columns.
+    pub fn column_picker(&mut self, ui: &mut egui::Ui) {
+        ui.menu_button("Columns", |ui| {
+            for column in Column::iter() {
+                let mut shown = self.layout.columns.contains(&column);
+                if ui.checkbox(&mut shown, column.to_string()).changed() {
+                    if shown {
+                        self.layout.columns.push(column);
+                    } else {
+                        self.layout.columns.retain(|c| *c != column);
+                        if self.layout.sort.is_some_and(|s| s.column == column) {
+                            self.layout.sort = None;
+                        }
+                    }
+                }
+            }
+        });
+    }
+
+    /// Sorts the rows by the sort column, if there is one. Only redone when the results or sort change.
+    fn order(&mut self, results: &ResultList) -> Option<&[usize]> {
+        let sort = self.layout.sort?;
+        let compare = |a: &CellValue, b: &CellValue| {
+            let ordering = a.cmp(b);
+            if sort.ascending {
+                ordering
+            } else {
+                ordering.reverse()
+            }
+        };
+
+        // When only a batch was merged in since, fit the new rows into the existing order
+        if let Some((revision, s, order)) = &mut self.order
+            && *s == sort
+            && let Some(merge) = results.last_merge()
+            && merge.revision == *revision
+        {
+            let rows = results.results();
+            for i in order.iter_mut() {
+                *i = merge.moved[*i];
+            }
+            for &new in &merge.inserted {
+                let key = sort.column.value(&rows[new].data);
+                // Equal rows stay in match order, as in a full sort
+                let at = order.partition_point(|&i| {
+                    compare(&sort.column.value(&rows[i].data), &key)
+                        .then(i.cmp(&new))
+                        .is_lt()
+                });
+                order.insert(at, new);
+            }
+            *revision = results.revision();
+        }
+
+        let is_stale = !matches!(
+            &self.order,
+            Some((revision, s, _)) if *revision == results.revision() && *s == sort
+        );
+        if is_stale {
+            let keys: Vec<CellValue> = results
+                .results()
+                .iter()
+                .map(|r| sort.column.value(&r.data))
+                .collect();
+            let mut order: Vec<usize> = (0..keys.len()).collect();
+            order.sort_by(|a, b| compare(&keys[*a], &keys[*b]));
+            self.order = Some((results.revision(), sort, order));
+        }
+        self.order.as_ref().map(|(_, _, order)| order.as_slice())
+    }
+
+    /// Clicking a header cycles between sorting ascending, descending and by match.
+    /// Clicking a row toggles its selection.
+    pub fn show(
+        &mut self,
+        ui: &mut egui::Ui,
+        results: &ResultList,
+        selected: &mut Vec<InvestmentData>,
+    ) {
+        let columns = self.layout.columns.clone();
+        let sort = self.layout.sort;
+        let order = self.order(results);
+        let mut clicked_column = None;
+
+        let mut builder = TableBuilder::new(ui)
+            .id_salt("result_table")
+            .striped(true)
+            .resizable(true)
+            .sense(Sense::click())
+            .cell_layout(Layout::left_to_right(Align::Center));
+        for i in 0..columns.len() {
+            builder = builder.column(if i + 1 == columns.len() {
+                TableColumn::remainder()
+            } else {
+                TableColumn::auto().at_least(40.0).clip(true)
+            });
+        }
+
+        builder
+            .header(ROW_HEIGHT, |mut header| {
+                for column in &columns {
+                    header.col(|ui| {
+                        let arrow = match sort {
+                            Some(s) if s.column == *column && s.ascending => " ⏶",
+                            Some(s) if s.column == *column => " ⏷",
+                            _ => "",
+                        };
+                        let label = Label::new(RichText::new(format!("{column}{arrow}")).strong())
+                            .sense(Sense::click());
+                        if ui.add(label).clicked() {
+                            clicked_column = Some(*column);
+                        }
+                    });
+                }
+            })
+            .body(|body| {
+                body.rows(ROW_HEIGHT, results.len(), |mut row| {
+                    let index = order.map_or(row.index(), |o| o[row.index()]);
+                    let data = &results.results()[index].data;
+                    row.set_selected(is_selected(selected, data));
+                    for column in &columns {
+                        row.col(|ui| {
+                            ui.add(Label::new(column.value(data).to_string()).truncate());
+                        });
+                    }
+                    if row.response().clicked() {
+                        toggle_selected(selected, data);
+                    }
+                });
+            });
+
+        if let Some(column) = clicked_column {
+            self.layout.sort = match sort {
+                Some(s) if s.column == column && s.ascending => Some(ColumnSort {
+                    column,
+                    ascending: false,
+                }),
+                Some(s) if s.column == column => None,
+                _ => Some(ColumnSort {
+                    column,
+                    ascending: true,
+                }),
+            };
+        }
+    }
+}
@@ -5,7 +5,10 @@ use eframe::{
 };
 use egui::{Color32, Pos2, RichText, Stroke, Ui, Vec2};
 use image::{DynamicImage, GenericImageView};
-use tiger_investment::data::image::{IconContainerType, texture::TextureHeader};
+use tiger_investment::{
+    data::image::{ColorblindMode, IconContainerType, InvestmentIcon, texture::TextureHeader},
+    global_instance::investment_manager,
+};
 use tiger_parse::PackageManagerExt;
 use tiger_pkg::{TagHash, package_manager};
 
@@ -181,7 +184,19 @@ pub fn icon_container(
     texture_cache: TextureCache,
     api_hash: u32,
 ) {
-    let img_container = ui.allocate_response(Vec2::new(96.0, 96.0), egui::Sense::click());
+    icon_container_sized(ui, 96.0, background_color, hashes, texture_cache, api_hash);
+}
+
+/// Same as [`icon_container`], but with a custom size. Returns the response of the icon so it can be clicked.
+pub fn icon_container_sized(
+    ui: &mut Ui,
+    size: f32,
+    background_color: Color32,
+    hashes: Vec<(TagHash, IconContainerType)>,
+    texture_cache: TextureCache,
+    api_hash: u32,
+) -> egui::Response {
+    let img_container = ui.allocate_response(Vec2::splat(size), egui::Sense::click());
 
     let img_rect = img_container.rect;
     let painter = ui.painter_at(img_rect);
@@ -211,6 +226,67 @@ pub fn icon_container(
                 egui::StrokeKind::Outside,
             );
         }
-        img_container.tag_context_with_texture(hashes.clone(), &texture_cache, api_hash);
+        img_container
+            .clone()
+            .tag_context_with_texture(hashes.clone(), &texture_cache, api_hash);
+    }
+
+    img_container
+}
+
+/// Returns the background and primary layers of an icon, in the order to render them.
+pub fn icon_layers(icon: &InvestmentIcon) -> Vec<(TagHash, IconContainerType)> {
+    let mut textures = Vec::new();
+    textures.extend(
+        icon.get_background_textures(Some(ColorblindMode::None))
+            .into_iter()
+            .map(|x| (x, IconContainerType::Background)),
+    );
+    textures.extend(
+        icon.get_primary_textures()
+            .into_iter()
+            .map(|x| (x, IconContainerType::Primary)),
+    );
+    textures
+}
+
+pub fn icon_background_color(icon: &InvestmentIcon) -> Color32 {
+    Color32::from_rgba_unmultiplied(
+        (icon.data.background_color[0] * 255.0) as u8,
+        (icon.data.background_color[1] * 255.0) as u8,
+        (icon.data.background_color[2] * 255.0) as u8,
+        (icon.data.background_color[3] * 255.0) as u8,
+    )
+}
+
+/// Draws an investment icon at `size`, from its index in the icon table.
+/// Draws an empty container if the icon doesn't exist.
+pub fn investment_icon(
+    ui: &mut Ui,
+    icon_index: i16,
+    size: f32,
+    texture_cache: TextureCache,
+    api_hash: u32,
+) -> egui::Response {
+    let icon = usize::try_from(icon_index)
+        .ok()
+        .and_then(|i| investment_manager().icons().get_index(i));
+    match icon {
+        Some(icon) => icon_container_sized(
+            ui,
+            size,
+            icon_background_color(&icon),
+            icon_layers(&icon),
+            texture_cache,
+            api_hash,
+        ),
+        None => icon_container_sized(
+            ui,
+            size,
+            Color32::from_black_alpha(127),
+            Vec::new(),
+            texture_cache,
+            api_hash,
+        ),
     }
 }